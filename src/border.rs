@@ -1,3 +1,6 @@
+use crate::style::Style;
+use crate::text::Alignment;
+
 /// A line around the box.
 pub const LINED: Border = Border {
     n: '─',
@@ -8,6 +11,102 @@ pub const LINED: Border = Border {
     nw: '┌',
     se: '┘',
     sw: '└',
+    n_tee: '┬',
+    s_tee: '┴',
+    e_tee: '┤',
+    w_tee: '├',
+    cross: '┼',
+    style: Style::new(),
+};
+
+/// A line around the box with rounded corners.
+pub const ROUNDED: Border = Border {
+    n: '─',
+    s: '─',
+    e: '│',
+    w: '│',
+    ne: '╮',
+    nw: '╭',
+    se: '╯',
+    sw: '╰',
+    n_tee: '┬',
+    s_tee: '┴',
+    e_tee: '┤',
+    w_tee: '├',
+    cross: '┼',
+    style: Style::new(),
+};
+
+/// A double line around the box.
+pub const DOUBLE: Border = Border {
+    n: '═',
+    s: '═',
+    e: '║',
+    w: '║',
+    ne: '╗',
+    nw: '╔',
+    se: '╝',
+    sw: '╚',
+    n_tee: '╦',
+    s_tee: '╩',
+    e_tee: '╣',
+    w_tee: '╠',
+    cross: '╬',
+    style: Style::new(),
+};
+
+/// A thick line around the box.
+pub const THICK: Border = Border {
+    n: '━',
+    s: '━',
+    e: '┃',
+    w: '┃',
+    ne: '┓',
+    nw: '┏',
+    se: '┛',
+    sw: '┗',
+    n_tee: '┳',
+    s_tee: '┻',
+    e_tee: '┫',
+    w_tee: '┣',
+    cross: '╋',
+    style: Style::new(),
+};
+
+/// A dashed line around the box.
+pub const DASHED: Border = Border {
+    n: '╌',
+    s: '╌',
+    e: '╎',
+    w: '╎',
+    ne: '┐',
+    nw: '┌',
+    se: '┘',
+    sw: '└',
+    n_tee: '┬',
+    s_tee: '┴',
+    e_tee: '┤',
+    w_tee: '├',
+    cross: '┼',
+    style: Style::new(),
+};
+
+/// A line around the box using only ASCII characters.
+pub const ASCII: Border = Border {
+    n: '-',
+    s: '-',
+    e: '|',
+    w: '|',
+    ne: '+',
+    nw: '+',
+    se: '+',
+    sw: '+',
+    n_tee: '+',
+    s_tee: '+',
+    e_tee: '+',
+    w_tee: '+',
+    cross: '+',
+    style: Style::new(),
 };
 
 /// Only a space around the [Rect].
@@ -20,9 +119,19 @@ pub const SPACE: Border = Border {
     nw: ' ',
     se: ' ',
     sw: ' ',
+    n_tee: ' ',
+    s_tee: ' ',
+    e_tee: ' ',
+    w_tee: ' ',
+    cross: ' ',
+    style: Style::new(),
 };
 
 /// The style of a border.
+/// The characters are written with the [Style], e.g. to color the border.
+/// The tees and the cross are used where borders of neighbouring sections meet, see
+/// [Layout.with_merged_borders]. `n_tee` is the junction in a northern edge (┬), `w_tee` the one
+/// in a western edge (├) and so on.
 #[derive(Clone)]
 pub struct Border {
     pub n: char,
@@ -33,4 +142,61 @@ pub struct Border {
     pub nw: char,
     pub se: char,
     pub sw: char,
+    pub n_tee: char,
+    pub s_tee: char,
+    pub e_tee: char,
+    pub w_tee: char,
+    pub cross: char,
+    pub style: Style,
+}
+
+impl Border {
+    /// Get a copy of the [Border] written with the given [Style], e.g. `border::LINED.with_style(style)`.
+    pub fn with_style(&self, style: Style) -> Border {
+        let mut border = self.clone();
+        border.style = style;
+        return border;
+    }
+
+    /// Get the character for a cell the lines of the border go from in the given directions.
+    pub(crate) fn junction(&self, north: bool, south: bool, east: bool, west: bool) -> char {
+        return match (north, south, east, west) {
+            (true, true, true, true) => self.cross,
+            (false, true, true, true) => self.n_tee,
+            (true, false, true, true) => self.s_tee,
+            (true, true, false, true) => self.e_tee,
+            (true, true, true, false) => self.w_tee,
+            (false, true, true, false) => self.nw,
+            (false, true, false, true) => self.ne,
+            (true, false, true, false) => self.sw,
+            (true, false, false, true) => self.se,
+            (true, _, false, false) | (_, true, false, false) => self.w,
+            _ => self.n,
+        };
+    }
+}
+
+/// A text shown in the top or bottom edge of a [Border], e.g. the name of the section.
+/// The text is surrounded by one space on each side and shortened with "..." if it does not fit.
+#[derive(Clone)]
+pub struct Title {
+    pub text: String,
+    pub alignment: Alignment,
+    pub style: Style,
+}
+
+impl Title {
+    /// Create a new [Title] written in the colors the terminal currently uses.
+    pub fn new(text: &str, alignment: Alignment) -> Title {
+        return Title::new_styled(text, alignment, Style::new());
+    }
+
+    /// Create a new [Title] written with the given [Style].
+    pub fn new_styled(text: &str, alignment: Alignment, style: Style) -> Title {
+        return Title {
+            text: String::from(text),
+            alignment,
+            style,
+        };
+    }
 }
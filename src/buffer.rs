@@ -0,0 +1,537 @@
+extern crate unicode_width;
+
+use crate::rect::Rect;
+use crate::text;
+
+use std::cmp;
+use std::io;
+use std::io::Write;
+use unicode_width::UnicodeWidthChar;
+
+/// An off-screen copy of the terminal.
+/// Everything written to the [Buffer] (e.g. by [Rect.write] or [ListRectColored.show]) is kept in
+/// memory. Flushing the [Buffer] compares it with the previously flushed frame and only writes
+/// the cells that changed to the terminal, in one batch.
+/// A [Buffer] can be used everywhere the terminal is used, as it implements [Write].
+pub struct Buffer<W: Write> {
+    output: W,
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+    previous: Vec<Cell>,
+    cursor: (u16, u16),
+    flushed_cursor: Option<(u16, u16)>,
+    saved_cursor: (u16, u16),
+    style: CellStyle,
+    state: ParseState,
+    pending: Vec<u8>,
+    passthrough: Vec<u8>,
+}
+
+/// A single character on the screen together with its style.
+#[derive(Clone, PartialEq)]
+struct Cell {
+    symbol: String,
+    style: CellStyle,
+}
+
+/// The style of a [Cell], given as the parameters of the escape sequences that set it.
+#[derive(Clone, Default, PartialEq)]
+struct CellStyle {
+    fg: Option<String>,
+    bg: Option<String>,
+    attributes: u16,
+}
+
+/// The state of parsing the escape sequences written to the [Buffer].
+enum ParseState {
+    GROUND,
+    /// After an escape, with the intermediate bytes read so far.
+    ESCAPE(String),
+    CSI(String),
+    /// A string like the title in an operating system command, ended by BEL or ST.
+    STRING(Vec<u8>),
+}
+
+impl<W: Write> Buffer<W> {
+    /// Creates a new [Buffer] with the size of the terminal.
+    /// Flushing the buffer will write to the given output.
+    pub fn new(output: W) -> Buffer<W> {
+        let (width, height) = Rect::terminal_size();
+        return Buffer {
+            output,
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+            previous: vec![],
+            cursor: (0, 0),
+            flushed_cursor: None,
+            saved_cursor: (0, 0),
+            style: CellStyle::default(),
+            state: ParseState::GROUND,
+            pending: vec![],
+            passthrough: vec![],
+        };
+    }
+
+    /// Get a reference to the output of the buffer.
+    pub fn get_ref(&self) -> &W {
+        return &self.output;
+    }
+
+    /// Get a mutable reference to the output of the buffer.
+    /// Everything written directly to the output bypasses the buffer.
+    pub fn get_mut(&mut self) -> &mut W {
+        return &mut self.output;
+    }
+
+    /// Forces the next flush to redraw the whole screen, e.g. after something else wrote to the
+    /// terminal.
+    pub fn invalidate(&mut self) {
+        self.previous = vec![];
+        self.flushed_cursor = None;
+    }
+
+    /// Changes the size of the buffer. The content that still fits will be kept.
+    /// This is done automatically on flush if the terminal size changed.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        let mut cells = vec![Cell::default(); width as usize * height as usize];
+        for y in 0..cmp::min(height, self.height) {
+            for x in 0..cmp::min(width, self.width) {
+                cells[y as usize * width as usize + x as usize] =
+                    self.cells[self.index(x, y)].clone();
+            }
+        }
+
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+        self.previous = vec![];
+        self.flushed_cursor = None;
+    }
+
+    fn index(&self, x: u16, y: u16) -> usize {
+        return y as usize * self.width as usize + x as usize;
+    }
+
+    /// Sets all cells in the given range of indices to empty cells.
+    fn erase(&mut self, from: usize, to: usize) {
+        let to = cmp::min(to, self.cells.len());
+        for i in from..to {
+            self.cells[i] = Cell::default();
+        }
+    }
+
+    /// Puts a character at the cursor and moves the cursor forward.
+    /// Wide characters take up two cells, the second one is left empty.
+    /// Characters without a width are added to the previous cell.
+    fn put(&mut self, c: char) {
+        match c {
+            '\n' => self.cursor.1 = self.cursor.1.saturating_add(1),
+            '\r' => self.cursor.0 = 0,
+            c if c.is_control() => {}
+            c => {
+                let (x, y) = self.cursor;
+                let width = UnicodeWidthChar::width(c).unwrap_or(0) as u16;
+                if y >= self.height || x > self.width || (x == self.width && width > 0) {
+                    self.cursor.0 = x.saturating_add(width);
+                    return;
+                }
+
+                if width == 0 {
+                    let mut i = self.index(x, y);
+                    while i > self.index(0, y) {
+                        i -= 1;
+                        if !self.cells[i].symbol.is_empty() {
+                            self.cells[i].symbol.push(c);
+                            break;
+                        }
+                    }
+                    return;
+                }
+
+                if width > 1 && x + 1 >= self.width {
+                    // A wide character does not fit in the last column.
+                    self.set(x, y, Cell::default());
+                } else {
+                    self.set(
+                        x,
+                        y,
+                        Cell {
+                            symbol: c.to_string(),
+                            style: self.style.clone(),
+                        },
+                    );
+                    if width > 1 {
+                        self.set(
+                            x + 1,
+                            y,
+                            Cell {
+                                symbol: String::new(),
+                                style: self.style.clone(),
+                            },
+                        );
+                    }
+                }
+                self.cursor.0 = x.saturating_add(width);
+            }
+        }
+    }
+
+    /// Sets a single cell. If only half of a wide character gets overwritten, the other half
+    /// will be cleared.
+    fn set(&mut self, x: u16, y: u16, cell: Cell) {
+        let i = self.index(x, y);
+        if self.cells[i].symbol.is_empty() && x > 0 && !cell.symbol.is_empty() {
+            self.cells[i - 1] = Cell::default();
+        }
+        if x + 1 < self.width && self.cells[i + 1].symbol.is_empty() {
+            self.cells[i + 1] = Cell::default();
+        }
+        self.cells[i] = cell;
+    }
+
+    /// Executes a complete control sequence with the given parameters and final character.
+    fn execute(&mut self, params: &str, action: char) {
+        let numbers: Vec<u16> = params
+            .split(';')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect();
+        let first = numbers[0];
+
+        if params.starts_with(|c: char| !c.is_ascii_digit() && c != ';') {
+            // Private sequences like hiding the cursor are given to the terminal on flush.
+            write!(self.passthrough, "\x1B[{}{}", params, action).unwrap();
+            return;
+        }
+
+        match action {
+            'H' | 'f' => {
+                let row = numbers.first().copied().unwrap_or(1);
+                let column = numbers.get(1).copied().unwrap_or(1);
+                self.cursor = (column.saturating_sub(1), row.saturating_sub(1));
+            }
+            'A' => self.cursor.1 = self.cursor.1.saturating_sub(cmp::max(first, 1)),
+            'B' => self.cursor.1 = self.cursor.1.saturating_add(cmp::max(first, 1)),
+            'C' => self.cursor.0 = self.cursor.0.saturating_add(cmp::max(first, 1)),
+            'D' => self.cursor.0 = self.cursor.0.saturating_sub(cmp::max(first, 1)),
+            'J' => {
+                let cursor = self.index(self.cursor.0, self.cursor.1);
+                match first {
+                    0 => self.erase(cursor, self.cells.len()),
+                    1 => self.erase(0, cursor + 1),
+                    _ => self.erase(0, self.cells.len()),
+                }
+            }
+            'K' => {
+                let start = self.index(0, self.cursor.1);
+                let cursor = self.index(self.cursor.0, self.cursor.1);
+                let end = self.index(0, self.cursor.1.saturating_add(1));
+                match first {
+                    0 => self.erase(cursor, end),
+                    1 => self.erase(start, cursor + 1),
+                    _ => self.erase(start, end),
+                }
+            }
+            'm' => self.style.apply(params),
+            's' => self.saved_cursor = self.cursor,
+            'u' => self.cursor = self.saved_cursor,
+            _ => write!(self.passthrough, "\x1B[{}{}", params, action).unwrap(),
+        }
+    }
+
+    /// Executes an escape sequence that is not a control sequence, e.g. saving the cursor.
+    /// Sequences that do not change the cells are given to the terminal on flush.
+    fn execute_escape(&mut self, intermediates: &str, action: char) {
+        match (intermediates, action) {
+            ("", '7') => self.saved_cursor = self.cursor,
+            ("", '8') => self.cursor = self.saved_cursor,
+            _ => write!(self.passthrough, "\x1B{}{}", intermediates, action).unwrap(),
+        }
+    }
+
+    /// Writes the text collected so far that forms complete characters.
+    fn put_pending(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        match std::str::from_utf8(&pending) {
+            Ok(text) => text.chars().for_each(|c| self.put(c)),
+            Err(error) => {
+                let (valid, rest) = pending.split_at(error.valid_up_to());
+                String::from_utf8_lossy(valid)
+                    .chars()
+                    .for_each(|c| self.put(c));
+                // Keep an incomplete character for the next write, drop invalid bytes.
+                if error.error_len().is_none() {
+                    self.pending = rest.to_vec();
+                }
+            }
+        }
+    }
+
+    /// Writes the changes since the last flush to the output.
+    fn draw(&mut self) -> io::Result<()> {
+        let (width, height) = Rect::terminal_size();
+        if (width, height) != (self.width, self.height) {
+            self.resize(width, height);
+        }
+
+        let mut out = vec![];
+        out.append(&mut self.passthrough);
+
+        let redraw = self.previous.len() != self.cells.len();
+        if redraw {
+            write!(out, "{}{}", termion::style::Reset, termion::clear::All)?;
+        }
+
+        let mut last_style = None;
+        let mut last_position = None;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = self.index(x, y);
+                let cell = &self.cells[i];
+                if !redraw && self.previous[i] == *cell {
+                    continue;
+                }
+                if (redraw && *cell == Cell::default()) || cell.symbol.is_empty() {
+                    continue;
+                }
+
+                if last_position != Some((x, y)) {
+                    write!(out, "{}", termion::cursor::Goto(x + 1, y + 1))?;
+                }
+                if last_style.as_ref() != Some(&cell.style) {
+                    write!(out, "{}", cell.style.sequence())?;
+                    last_style = Some(cell.style.clone());
+                }
+                write!(out, "{}", cell.symbol)?;
+                last_position = Some((x + cmp::max(text::width(&cell.symbol), 1) as u16, y));
+            }
+        }
+
+        if last_style.is_some() {
+            write!(out, "{}", termion::style::Reset)?;
+        }
+        // The cursor is also moved if nothing else changed, e.g. in an input field.
+        if !out.is_empty() || self.flushed_cursor != Some(self.cursor) {
+            write!(
+                out,
+                "{}",
+                termion::cursor::Goto(self.cursor.0 + 1, self.cursor.1 + 1)
+            )?;
+        }
+
+        self.previous = self.cells.clone();
+        self.flushed_cursor = Some(self.cursor);
+        self.output.write_all(&out)?;
+        return self.output.flush();
+    }
+}
+
+impl<W: Write> Write for Buffer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &byte in buf {
+            match &mut self.state {
+                ParseState::GROUND => {
+                    if byte == 0x1B {
+                        self.put_pending();
+                        self.state = ParseState::ESCAPE(String::new());
+                    } else {
+                        self.pending.push(byte);
+                    }
+                }
+                ParseState::ESCAPE(intermediates) => match byte {
+                    b'[' if intermediates.is_empty() => self.state = ParseState::CSI(String::new()),
+                    b']' | b'P' | b'X' | b'^' | b'_' if intermediates.is_empty() => {
+                        self.state = ParseState::STRING(vec![byte]);
+                    }
+                    0x20..=0x2F => intermediates.push(byte as char),
+                    0x30..=0x7E => {
+                        let intermediates = std::mem::take(intermediates);
+                        self.state = ParseState::GROUND;
+                        self.execute_escape(&intermediates, byte as char);
+                    }
+                    _ => self.state = ParseState::GROUND,
+                },
+                ParseState::STRING(string) => {
+                    // The string ends with BEL or with ST, which is ESC followed by a backslash.
+                    let end = byte == 0x07 || (byte == b'\\' && string.last() == Some(&0x1B));
+                    string.push(byte);
+                    if end {
+                        self.passthrough.push(0x1B);
+                        self.passthrough.append(string);
+                        self.state = ParseState::GROUND;
+                    }
+                }
+                ParseState::CSI(params) => {
+                    if (0x40..=0x7E).contains(&byte) {
+                        let params = std::mem::take(params);
+                        self.state = ParseState::GROUND;
+                        self.execute(&params, byte as char);
+                    } else {
+                        params.push(byte as char);
+                    }
+                }
+            }
+        }
+        self.put_pending();
+        return Ok(buf.len());
+    }
+
+    /// Writes all changes since the last flush to the terminal.
+    fn flush(&mut self) -> io::Result<()> {
+        return self.draw();
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Cell {
+        return Cell {
+            symbol: String::from(" "),
+            style: CellStyle::default(),
+        };
+    }
+}
+
+impl CellStyle {
+    /// Applies the parameters of a "select graphic rendition" sequence.
+    fn apply(&mut self, params: &str) {
+        let params: Vec<&str> = params.split(';').collect();
+        let mut i = 0;
+
+        while i < params.len() {
+            let code: u16 = params[i].parse().unwrap_or(0);
+            match code {
+                0 => *self = CellStyle::default(),
+                1..=9 => self.attributes |= 1 << code,
+                22 => self.attributes &= !(1 << 1 | 1 << 2),
+                21..=29 => self.attributes &= !(1 << (code - 20)),
+                30..=37 | 90..=97 => self.fg = Some(code.to_string()),
+                40..=47 | 100..=107 => self.bg = Some(code.to_string()),
+                39 => self.fg = None,
+                49 => self.bg = None,
+                38 | 48 => {
+                    // Extended colors consume either "5;n" or "2;r;g;b".
+                    let length = if params.get(i + 1) == Some(&"2") { 5 } else { 3 };
+                    let end = cmp::min(i + length, params.len());
+                    let color = Some(params[i..end].join(";"));
+                    if code == 38 {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                    i = end;
+                    continue;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    /// The escape sequence that resets the terminal to this style.
+    fn sequence(&self) -> String {
+        let mut params = vec![String::from("0")];
+        params.extend((1..=9).filter(|a| self.attributes & (1 << a) != 0).map(|a: u16| a.to_string()));
+        params.extend(self.fg.clone());
+        params.extend(self.bg.clone());
+        return format!("\x1B[{}m", params.join(";"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Get the symbols of the first cells of the row.
+    fn row(buffer: &Buffer<Vec<u8>>, y: u16, columns: u16) -> Vec<&str> {
+        return (0..columns)
+            .map(|x| buffer.cells[buffer.index(x, y)].symbol.as_str())
+            .collect();
+    }
+
+    /// Flushes the buffer and returns what was written to the output.
+    fn flush(buffer: &mut Buffer<Vec<u8>>) -> String {
+        buffer.get_mut().clear();
+        buffer.flush().unwrap();
+        return String::from_utf8(buffer.get_ref().clone()).unwrap();
+    }
+
+    #[test]
+    fn identical_frame_flushes_nothing() {
+        let mut buffer = Buffer::new(vec![]);
+        write!(buffer, "{}hello", termion::cursor::Goto(1, 1)).unwrap();
+        assert!(!flush(&mut buffer).is_empty());
+        write!(buffer, "{}hello", termion::cursor::Goto(1, 1)).unwrap();
+        assert_eq!(flush(&mut buffer), "");
+    }
+
+    #[test]
+    fn cursor_move_is_flushed() {
+        let mut buffer = Buffer::new(vec![]);
+        write!(buffer, "{}hello", termion::cursor::Goto(1, 1)).unwrap();
+        flush(&mut buffer);
+        write!(buffer, "{}", termion::cursor::Goto(4, 1)).unwrap();
+        assert_eq!(flush(&mut buffer), termion::cursor::Goto(4, 1).to_string());
+    }
+
+    #[test]
+    fn wide_characters_take_two_cells() {
+        let mut buffer = Buffer::new(vec![]);
+        write!(buffer, "{}日a", termion::cursor::Goto(1, 1)).unwrap();
+        assert_eq!(row(&buffer, 0, 3), ["日", "", "a"]);
+        assert_eq!(buffer.cursor, (3, 0));
+
+        // Overwriting the second half clears the first one.
+        write!(buffer, "{}x", termion::cursor::Goto(2, 1)).unwrap();
+        assert_eq!(row(&buffer, 0, 3), [" ", "x", "a"]);
+    }
+
+    #[test]
+    fn extended_colors_are_parsed() {
+        let mut buffer = Buffer::new(vec![]);
+        write!(buffer, "{}\x1B[1;38;5;196;48;2;1;2;3ma", termion::cursor::Goto(1, 1)).unwrap();
+        let style = &buffer.cells[0].style;
+        assert_eq!(style.fg.as_deref(), Some("38;5;196"));
+        assert_eq!(style.bg.as_deref(), Some("48;2;1;2;3"));
+        assert_eq!(style.attributes, 1 << 1);
+
+        write!(buffer, "\x1B[39;22mb").unwrap();
+        assert_eq!(buffer.cells[1].style.fg, None);
+        assert_eq!(buffer.cells[1].style.bg.as_deref(), Some("48;2;1;2;3"));
+        assert_eq!(buffer.cells[1].style.attributes, 0);
+    }
+
+    #[test]
+    fn lines_and_screen_are_erased() {
+        let mut buffer = Buffer::new(vec![]);
+        write!(buffer, "{}abc{}def", termion::cursor::Goto(1, 1), termion::cursor::Goto(1, 2)).unwrap();
+        write!(buffer, "{}{}", termion::cursor::Goto(2, 1), termion::clear::UntilNewline).unwrap();
+        assert_eq!(row(&buffer, 0, 3), ["a", " ", " "]);
+        assert_eq!(row(&buffer, 1, 3), ["d", "e", "f"]);
+
+        write!(buffer, "{}", termion::clear::All).unwrap();
+        assert_eq!(row(&buffer, 0, 3), [" ", " ", " "]);
+        assert_eq!(row(&buffer, 1, 3), [" ", " ", " "]);
+    }
+
+    #[test]
+    fn strings_are_not_written_to_cells() {
+        let mut buffer = Buffer::new(vec![]);
+        write!(buffer, "{}\x1B]0;title\x07a\x1B]0;other\x1B\\b", termion::cursor::Goto(1, 1)).unwrap();
+        assert_eq!(row(&buffer, 0, 3), ["a", "b", " "]);
+        let output = flush(&mut buffer);
+        assert!(output.starts_with("\x1B]0;title\x07\x1B]0;other\x1B\\"));
+    }
+
+    #[test]
+    fn cursor_is_saved_and_restored() {
+        let mut buffer = Buffer::new(vec![]);
+        write!(buffer, "{}{}", termion::cursor::Goto(5, 2), termion::cursor::Save).unwrap();
+        write!(buffer, "{}x{}y", termion::cursor::Goto(1, 1), termion::cursor::Restore).unwrap();
+        assert_eq!(row(&buffer, 0, 1), ["x"]);
+        assert_eq!(row(&buffer, 1, 5), [" ", " ", " ", " ", "y"]);
+
+        write!(buffer, "\x1B7{}\x1B8z", termion::cursor::Goto(1, 1)).unwrap();
+        assert_eq!(row(&buffer, 1, 6), [" ", " ", " ", " ", "y", "z"]);
+    }
+}
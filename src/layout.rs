@@ -1,6 +1,12 @@
 extern crate termion;
 
 use crate::border;
+use crate::style::Style;
+
+use std::cmp;
+use std::collections::HashSet;
+use std::error;
+use std::fmt;
 
 /// Represents a rectangular layout.
 #[derive(Clone)]
@@ -9,25 +15,80 @@ pub struct Layout {
     pub(crate) split: Option<Box<Split>>,
     pub(crate) has_border: bool,
     pub(crate) border: Option<border::Border>,
+    pub(crate) title: Option<border::Title>,
+    pub(crate) footer: Option<border::Title>,
+    pub(crate) merge_borders: bool,
+    pub(crate) sides: Sides<bool>,
+    pub(crate) padding: Sides<u16>,
+    pub(crate) margin: Sides<u16>,
+    pub(crate) style: Style,
+}
+
+/// Represents a value for each side of a [Layout], e.g. which sides have a border.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Sides<T> {
+    pub north: T,
+    pub south: T,
+    pub east: T,
+    pub west: T,
 }
 
-/// Represents a split of a [Layout].
+/// Represents a split of a [Layout] into any number of [Layout]s.
+/// Every [Layout] of the split has a [Constraint] describing how much space it gets.
 #[derive(Clone)]
 pub(crate) struct Split {
-    pub(crate) rects: (Layout, Layout),
-    pub(crate) split_type: SplitType,
+    pub(crate) rects: Vec<(Layout, Constraint)>,
     pub(crate) direction: SplitDirection,
 }
 
-/// Represents the type of the split.
-/// The split can either be done with percentages (using f32) of
-/// by given the amount of rows/columns reserved by one of the [Layout]s.
-/// If given a positive number the first one will have the wanted amount of rows/columns
-/// otherwise the second one.
+/// Represents how much space a [Layout] in a split gets.
+/// The percentage is given as a fraction (e.g. 0.5 for half of the space), the ratio as
+/// numerator and denominator of the space.
+/// Lengths, minimums and maximums are the amount of rows/columns that can be written to, the space
+/// of the border, padding and margin will be added to them. For a merged [Layout], this is the
+/// space of all [Layout]s merged along the split, e.g. both borders of two [Layout]s next to each
+/// other, or one less if they share an edge.
+/// The remaining space after all exact sizes is distributed over the flexible [Layout]s according
+/// to their weights. A fill has the given weight, a minimum or maximum alone has a weight of one.
+/// Multiple constraints can be combined with `ALL`, e.g. `ALL(vec![PERCENTAGE(0.3), MIN(20)])`
+/// for 30% of the space but at least 20 columns.
+///
+/// When not all constraints can be satisfied, minimums win over maximums and both win over exact
+/// sizes. If there is not enough space for all [Layout]s, the last ones will be shrunk first.
 #[derive(Clone)]
-pub(crate) enum SplitType {
+pub enum Constraint {
+    PERCENTAGE(f32),
+    RATIO(u32, u32),
+    LENGTH(u16),
+    MIN(u16),
+    MAX(u16),
+    FILL(u16),
+    ALL(Vec<Constraint>),
+}
+
+/// The resolved requirements of a [Constraint] for a given amount of space.
+struct Bounds {
+    min: u16,
+    max: u16,
+    exact: Option<f32>,
+    weight: u16,
+}
+
+/// Represents the reasons a [Layout] cannot be converted to [Rect]s.
+/// `SPACE` is returned if there is not enough space, the name is missing if a split does not fit.
+/// `UNNAMED` is returned for a [Layout] without a name and `EMPTY` for a merge of no [Layout]s.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LayoutError {
+    SPACE {
+        name: Option<String>,
+        needed: u16,
+        available: u16,
+    },
+    DUPLICATE(String),
+    UNNAMED,
+    EMPTY,
     PERCENTAGE(f32),
-    VALUE(i16),
+    RATIO(u32, u32),
 }
 
 /// Represents in what direction to split.
@@ -51,6 +112,13 @@ impl Layout {
             name: Some(name),
             has_border: false,
             border: None,
+            title: None,
+            footer: None,
+            merge_borders: false,
+            sides: Sides::all(true),
+            padding: Sides::all(0),
+            margin: Sides::all(0),
+            style: Style::new(),
         };
     }
 
@@ -66,36 +134,565 @@ impl Layout {
             name: Some(name),
             has_border: true,
             border: Some(border),
+            title: None,
+            footer: None,
+            merge_borders: false,
+            sides: Sides::all(true),
+            padding: Sides::all(0),
+            margin: Sides::all(0),
+            style: Style::new(),
         };
     }
 
     /// Merges two [Layout] using percentages. The new layout will be returned
     pub fn merge(r1: Layout, r2: Layout, direction: SplitDirection, percentage: f32) -> Layout {
-        let has_border = r1.has_border || r2.has_border;
-        Layout {
-            name: None,
-            split: Some(Box::new(Split {
-                rects: (r1, r2),
-                split_type: SplitType::PERCENTAGE(percentage),
-                direction: direction,
-            })),
-            has_border: has_border,
-            border: None,
-        }
+        return Layout::merge_many(
+            vec![
+                (r1, Constraint::PERCENTAGE(percentage)),
+                (r2, Constraint::FILL(1)),
+            ],
+            direction,
+        );
     }
 
     /// Merges two [Layout] using a specified value for the rows/columns. The new layout will be returned
+    /// If given a positive number the first one will have the wanted amount of rows/columns
+    /// otherwise the second one.
+    /// The value does not include borders, padding and margin, see [Constraint]. If that
+    /// [Layout] is merged itself, the borders of all [Layout]s in it are added.
     pub fn merge_value(r1: Layout, r2: Layout, direction: SplitDirection, value: i16) -> Layout {
-        let has_border = r1.has_border || r2.has_border;
+        let rects = if value >= 0 {
+            vec![
+                (r1, Constraint::LENGTH(value as u16)),
+                (r2, Constraint::FILL(1)),
+            ]
+        } else {
+            vec![
+                (r1, Constraint::FILL(1)),
+                (r2, Constraint::LENGTH(value.unsigned_abs())),
+            ]
+        };
+        return Layout::merge_many(rects, direction);
+    }
+
+    /// Merges any number of [Layout]s next to each other. Each [Layout] is given with a
+    /// [Constraint] for its size. The new layout will be returned
+    pub fn merge_many(rects: Vec<(Layout, Constraint)>, direction: SplitDirection) -> Layout {
+        let has_border = rects.iter().any(|(r, _)| r.has_border);
         Layout {
             name: None,
-            split: Some(Box::new(Split {
-                rects: (r1, r2),
-                split_type: SplitType::VALUE(value),
-                direction: direction,
-            })),
-            has_border: has_border,
+            split: Some(Box::new(Split { rects, direction })),
+            has_border,
             border: None,
+            title: None,
+            footer: None,
+            merge_borders: false,
+            sides: Sides::all(true),
+            padding: Sides::all(0),
+            margin: Sides::all(0),
+            style: Style::new(),
+        }
+    }
+
+    /// Sets the [Title] shown in the top edge of the [Border].
+    /// It is only shown for a [Layout] with a [Border] that is not merged from other [Layout]s.
+    pub fn with_title(mut self, title: border::Title) -> Layout {
+        self.title = Some(title);
+        return self;
+    }
+
+    /// Sets the [Title] shown in the bottom edge of the [Border], like [Layout.with_title].
+    pub fn with_footer(mut self, footer: border::Title) -> Layout {
+        self.footer = Some(footer);
+        return self;
+    }
+
+    /// Lets all [Layout]s merged into this one share the edges between their [Border]s instead of
+    /// drawing two borders next to each other. Where the borders meet, junctions like ┬ or ┼ are
+    /// drawn. Only neighbours that both have a border share an edge.
+    pub fn with_merged_borders(mut self) -> Layout {
+        self.merge_borders = true;
+        return self;
+    }
+
+    /// Get the names of all [Layout]s in the order they were merged.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        let mut names = vec![];
+        self.collect_names(&mut names);
+        return names.into_iter();
+    }
+
+    fn collect_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match &self.split {
+            Some(split) => {
+                for (layout, _) in split.rects.iter() {
+                    layout.collect_names(names);
+                }
+            }
+            None => names.extend(self.name.as_deref()),
+        }
+    }
+
+    /// Checks the [Layout] for duplicate names, [Layout]s without name, empty merges and invalid
+    /// constraints. All errors that were found will be returned.
+    pub fn validate(&self) -> Result<(), Vec<LayoutError>> {
+        let mut errors = vec![];
+        self.collect_errors(&mut HashSet::new(), &mut errors);
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+        return Err(errors);
+    }
+
+    fn collect_errors<'a>(&'a self, names: &mut HashSet<&'a str>, errors: &mut Vec<LayoutError>) {
+        match &self.split {
+            Some(split) => {
+                if split.rects.is_empty() {
+                    errors.push(LayoutError::EMPTY);
+                }
+                for (layout, constraint) in split.rects.iter() {
+                    if let Err(error) = constraint.check() {
+                        errors.push(error);
+                    }
+                    layout.collect_errors(names, errors);
+                }
+            }
+            None => match &self.name {
+                Some(name) => {
+                    let error = LayoutError::DUPLICATE(name.clone());
+                    if !names.insert(name) && !errors.contains(&error) {
+                        errors.push(error);
+                    }
+                }
+                None => errors.push(LayoutError::UNNAMED),
+            },
+        }
+    }
+
+    /// Only draws the [Border] on the given sides, e.g. only a line on the top.
+    /// The area that can be written to grows by the sides without a border.
+    pub fn with_sides(mut self, sides: Sides<bool>) -> Layout {
+        self.sides = sides;
+        return self;
+    }
+
+    /// Adds empty space between the [Border] and the area that can be written to.
+    /// Only used for [Layout]s that are not merged from other [Layout]s.
+    pub fn with_padding(mut self, padding: Sides<u16>) -> Layout {
+        self.padding = padding;
+        return self;
+    }
+
+    /// Adds empty space around the [Border], e.g. to separate it from the neighbours.
+    /// Only used for [Layout]s that are not merged from other [Layout]s.
+    pub fn with_margin(mut self, margin: Sides<u16>) -> Layout {
+        self.margin = margin;
+        return self;
+    }
+
+    /// Sets the default [Style] of the section, e.g. its background color.
+    /// Everything written to the [Rect] without own colors uses it.
+    pub fn with_style(mut self, style: Style) -> Layout {
+        self.style = style;
+        return self;
+    }
+
+    /// Get the sides on which the [Border] is at the edge of the [Layout], so it can be shared
+    /// with a neighbour.
+    pub(crate) fn outer_borders(&self) -> Sides<bool> {
+        if let Some(split) = &self.split {
+            // The first and last layout make up the edges in the direction of the split, all
+            // layouts together the edges across it.
+            let borders: Vec<Sides<bool>> = split.rects.iter().map(|(r, _)| r.outer_borders()).collect();
+            let all = |side: fn(&Sides<bool>) -> bool| !borders.is_empty() && borders.iter().all(side);
+            let first = borders.first().copied().unwrap_or(Sides::all(false));
+            let last = borders.last().copied().unwrap_or(Sides::all(false));
+            return match split.direction {
+                SplitDirection::HORIZONTAL => Sides {
+                    north: all(|b| b.north),
+                    south: all(|b| b.south),
+                    east: last.east,
+                    west: first.west,
+                },
+                SplitDirection::VERTICAL => Sides {
+                    north: first.north,
+                    south: last.south,
+                    east: all(|b| b.east),
+                    west: all(|b| b.west),
+                },
+            };
+        }
+        let outer = |side: bool, margin: u16| self.has_border && side && margin == 0;
+        return Sides {
+            north: outer(self.sides.north, self.margin.north),
+            south: outer(self.sides.south, self.margin.south),
+            east: outer(self.sides.east, self.margin.east),
+            west: outer(self.sides.west, self.margin.west),
+        };
+    }
+
+    /// The amount of rows/columns between each side of the [Layout] and its writable area.
+    pub(crate) fn insets(&self) -> Sides<u16> {
+        let inset = |side: bool, padding: u16, margin: u16| {
+            ((self.has_border && side) as u16)
+                .saturating_add(padding)
+                .saturating_add(margin)
+        };
+        return Sides {
+            north: inset(self.sides.north, self.padding.north, self.margin.north),
+            south: inset(self.sides.south, self.padding.south, self.margin.south),
+            east: inset(self.sides.east, self.padding.east, self.margin.east),
+            west: inset(self.sides.west, self.padding.west, self.margin.west),
+        };
+    }
+
+    /// The amount of rows/columns the borders, padding and margin take up in the direction of a
+    /// split. For a merged [Layout], the insets of its children add up along its own direction,
+    /// without the edges they share if `merged`, across it the largest one is taken.
+    pub(crate) fn inset(&self, direction: &SplitDirection, merged: bool) -> u16 {
+        if let Some(split) = &self.split {
+            let merged = merged || self.merge_borders;
+            let insets = split.rects.iter().map(|(r, _)| r.inset(direction, merged));
+            return match (&split.direction, direction) {
+                (SplitDirection::HORIZONTAL, SplitDirection::HORIZONTAL)
+                | (SplitDirection::VERTICAL, SplitDirection::VERTICAL) => {
+                    let shared = split.shared(merged).iter().filter(|s| **s).count() as u16;
+                    insets.fold(0, u16::saturating_add).saturating_sub(shared)
+                }
+                _ => insets.max().unwrap_or(0),
+            };
+        }
+        let insets = self.insets();
+        return match direction {
+            SplitDirection::HORIZONTAL => insets.west.saturating_add(insets.east),
+            SplitDirection::VERTICAL => insets.north.saturating_add(insets.south),
+        };
+    }
+}
+
+impl<T: Copy> Sides<T> {
+    /// Create new [Sides] with a value for each side.
+    pub fn new(north: T, south: T, east: T, west: T) -> Sides<T> {
+        return Sides {
+            north,
+            south,
+            east,
+            west,
+        };
+    }
+
+    /// Create new [Sides] with the same value on all sides.
+    pub fn all(value: T) -> Sides<T> {
+        return Sides::new(value, value, value, value);
+    }
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LayoutError::SPACE {
+                name,
+                needed,
+                available,
+            } => write!(
+                f,
+                "Not enough space for {}: needs {} rows/columns but only {} are available",
+                name.as_deref().unwrap_or("the split"),
+                needed,
+                available
+            ),
+            LayoutError::DUPLICATE(name) => write!(f, "The name {} is used more than once", name),
+            LayoutError::UNNAMED => write!(f, "A layout has no name"),
+            LayoutError::EMPTY => write!(f, "A layout merges no layouts"),
+            LayoutError::PERCENTAGE(percentage) => {
+                write!(f, "The percentage {} is not between 0 and 1", percentage)
+            }
+            LayoutError::RATIO(numerator, denominator) => write!(
+                f,
+                "The ratio {}/{} is not between 0 and 1",
+                numerator, denominator
+            ),
+        }
+    }
+}
+
+impl error::Error for LayoutError {}
+
+impl Constraint {
+    /// Resolves the constraint for the given total space and the space taken by the border.
+    fn bounds(&self, total: u16, inset: u16) -> Bounds {
+        let mut bounds = Bounds {
+            min: 0,
+            max: u16::MAX,
+            exact: None,
+            weight: 0,
+        };
+
+        match self {
+            Constraint::PERCENTAGE(percentage) => {
+                bounds.exact = Some(total as f32 * percentage.max(0.0))
+            }
+            Constraint::RATIO(numerator, denominator) => {
+                bounds.exact = Some(if *denominator == 0 {
+                    0.0
+                } else {
+                    total as f32 * *numerator as f32 / *denominator as f32
+                })
+            }
+            Constraint::LENGTH(length) => bounds.exact = Some(length.saturating_add(inset) as f32),
+            Constraint::MIN(min) => {
+                bounds.min = min.saturating_add(inset);
+                bounds.weight = 1;
+            }
+            Constraint::MAX(max) => {
+                bounds.max = max.saturating_add(inset);
+                bounds.weight = 1;
+            }
+            Constraint::FILL(weight) => bounds.weight = *weight,
+            Constraint::ALL(constraints) => {
+                let mut has_fill = false;
+                for constraint in constraints {
+                    let other = constraint.bounds(total, inset);
+                    bounds.min = cmp::max(bounds.min, other.min);
+                    bounds.max = cmp::min(bounds.max, other.max);
+                    bounds.exact = bounds.exact.or(other.exact);
+                    if let Constraint::FILL(weight) = constraint {
+                        has_fill = true;
+                        bounds.weight = bounds.weight.saturating_add(*weight);
+                    }
+                }
+                if !has_fill {
+                    bounds.weight = bounds.exact.is_none() as u16;
+                }
+            }
+        }
+
+        return bounds;
+    }
+
+    /// Checks that the percentages and ratios of the constraint are valid.
+    fn check(&self) -> Result<(), LayoutError> {
+        match self {
+            Constraint::PERCENTAGE(percentage) if !(0.0..=1.0).contains(percentage) => {
+                return Err(LayoutError::PERCENTAGE(*percentage));
+            }
+            Constraint::RATIO(numerator, denominator)
+                if *denominator == 0 || numerator > denominator =>
+            {
+                return Err(LayoutError::RATIO(*numerator, *denominator));
+            }
+            Constraint::ALL(constraints) => {
+                for constraint in constraints {
+                    constraint.check()?;
+                }
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+}
+
+impl Split {
+    /// Whether each [Layout] of the split shares its first edge with the one before. This is
+    /// only done if the split is `merged` and both have a border at that edge.
+    pub(crate) fn shared(&self, merged: bool) -> Vec<bool> {
+        return (0..self.rects.len())
+            .map(|i| {
+                if !merged || i == 0 {
+                    return false;
+                }
+                let (before, after) = (&self.rects[i - 1].0, &self.rects[i].0);
+                return match self.direction {
+                    SplitDirection::HORIZONTAL => {
+                        before.outer_borders().east && after.outer_borders().west
+                    }
+                    SplitDirection::VERTICAL => {
+                        before.outer_borders().south && after.outer_borders().north
+                    }
+                };
+            })
+            .collect();
+    }
+
+    /// Checks that the split fits in the given amount of rows/columns.
+    /// `merged` tells whether the borders of its [Layout]s are merged.
+    pub(crate) fn check(&self, total: u16, merged: bool) -> Result<(), LayoutError> {
+        let mut needed: u32 = 0;
+        for (layout, constraint) in self.rects.iter() {
+            let bounds = constraint.bounds(total, layout.inset(&self.direction, merged));
+            needed += match bounds.exact {
+                Some(exact) => cmp::max(cmp::min(exact as u16, bounds.max), bounds.min),
+                None => bounds.min,
+            } as u32;
         }
+
+        if needed > total as u32 {
+            return Err(LayoutError::SPACE {
+                name: None,
+                needed: cmp::min(needed, u16::MAX as u32) as u16,
+                available: total,
+            });
+        }
+        return Ok(());
+    }
+
+    /// Distributes the given amount of rows/columns to the [Layout]s of the split.
+    /// Exact sizes are rounded such that they add up correctly, the remaining space
+    /// is given to the flexible [Layout]s.
+    pub(crate) fn sizes(&self, total: u16, merged: bool) -> Vec<u16> {
+        let bounds: Vec<Bounds> = self
+            .rects
+            .iter()
+            .map(|(layout, constraint)| constraint.bounds(total, layout.inset(&self.direction, merged)))
+            .collect();
+        return Bounds::distribute(&bounds, total);
+    }
+}
+
+impl Constraint {
+    /// Distributes the given amount of rows/columns to the constraints, the same way as the
+    /// [Layout]s of a split without borders, e.g. for the columns of a table.
+    pub(crate) fn sizes(constraints: &[Constraint], total: u16) -> Vec<u16> {
+        let bounds: Vec<Bounds> = constraints.iter().map(|c| c.bounds(total, 0)).collect();
+        return Bounds::distribute(&bounds, total);
+    }
+}
+
+impl Bounds {
+    /// Distributes the given amount of rows/columns according to the bounds.
+    fn distribute(bounds: &[Bounds], total: u16) -> Vec<u16> {
+        let mut sizes: Vec<u16> = bounds
+            .iter()
+            .map(|b| b.exact.map(|e| e as u16).unwrap_or(b.min))
+            .collect();
+
+        // Give the rows/columns lost by rounding down to the exact sizes with the biggest fractions.
+        let mut fractions: Vec<(usize, f32)> = bounds
+            .iter()
+            .enumerate()
+            .filter_map(|(i, b)| b.exact.map(|e| (i, e - e.floor())))
+            .collect();
+        let exact_sum: f32 = bounds.iter().filter_map(|b| b.exact).sum();
+        let rounded_sum: u32 = fractions.iter().map(|(i, _)| sizes[*i] as u32).sum();
+        let lost = (exact_sum.round() as u32).saturating_sub(rounded_sum);
+        fractions.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(cmp::Ordering::Equal));
+        for (i, _) in fractions.iter().take(lost as usize) {
+            sizes[*i] = sizes[*i].saturating_add(1);
+        }
+
+        // Keep the exact sizes in their bounds, the minimum wins over the maximum.
+        for (size, b) in sizes.iter_mut().zip(bounds.iter()) {
+            if b.exact.is_some() {
+                *size = cmp::max(cmp::min(*size, b.max), b.min);
+            }
+        }
+
+        // Never give out more than available, the last ones are shrunk first.
+        let mut available = total;
+        for size in sizes.iter_mut() {
+            *size = cmp::min(*size, available);
+            available -= *size;
+        }
+
+        // Distribute the rest to the flexible ones according to their weights.
+        // Whenever one reaches its maximum, the rest is distributed again without it.
+        while available > 0 {
+            let open: Vec<usize> = (0..sizes.len())
+                .filter(|i| {
+                    bounds[*i].exact.is_none() && bounds[*i].weight > 0 && sizes[*i] < bounds[*i].max
+                })
+                .collect();
+            let weight_sum: u32 = open.iter().map(|i| bounds[*i].weight as u32).sum();
+            if weight_sum == 0 {
+                break;
+            }
+
+            let mut given = 0;
+            let mut capped = false;
+            let mut remainders = vec![];
+            for i in open.iter() {
+                let portion = available as u32 * bounds[*i].weight as u32;
+                let mut share = (portion / weight_sum) as u16;
+                remainders.push((*i, portion % weight_sum));
+                if share >= bounds[*i].max - sizes[*i] {
+                    share = bounds[*i].max - sizes[*i];
+                    capped = true;
+                }
+                sizes[*i] += share;
+                given += share;
+            }
+            available -= given;
+
+            if !capped {
+                // The rows/columns left by rounding down go to the biggest remainders.
+                remainders.sort_by_key(|(_, remainder)| cmp::Reverse(*remainder));
+                for (i, _) in remainders.iter().take(available as usize) {
+                    sizes[*i] += 1;
+                }
+                break;
+            }
+        }
+
+        return sizes;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        let cases: Vec<(Vec<Constraint>, u16, Vec<u16>)> = vec![
+            (vec![Constraint::FILL(1); 5], 12, vec![3, 3, 2, 2, 2]),
+            (vec![Constraint::FILL(1), Constraint::FILL(2)], 10, vec![3, 7]),
+            (vec![Constraint::PERCENTAGE(1.0 / 3.0); 3], 10, vec![4, 3, 3]),
+            (vec![Constraint::RATIO(1, 4), Constraint::FILL(1)], 10, vec![3, 7]),
+            (
+                vec![
+                    Constraint::ALL(vec![Constraint::MIN(20), Constraint::PERCENTAGE(0.3)]),
+                    Constraint::FILL(1),
+                ],
+                40,
+                vec![20, 20],
+            ),
+            (vec![Constraint::MAX(3); 2], 10, vec![3, 3]),
+            (vec![Constraint::MAX(3), Constraint::FILL(1)], 10, vec![3, 7]),
+            (vec![Constraint::MIN(8), Constraint::FILL(1)], 10, vec![9, 1]),
+            (vec![Constraint::LENGTH(4), Constraint::FILL(1)], 10, vec![4, 6]),
+        ];
+        for (i, (constraints, total, expected)) in cases.into_iter().enumerate() {
+            assert_eq!(Constraint::sizes(&constraints, total), expected, "case {}", i);
+        }
+    }
+
+    #[test]
+    fn sizes_of_overfull_split() {
+        let constraints = vec![Constraint::LENGTH(6), Constraint::LENGTH(6), Constraint::FILL(1)];
+        let sizes = Constraint::sizes(&constraints, 10);
+        assert_eq!(sizes, vec![6, 4, 0]);
+        assert_eq!(Constraint::sizes(&constraints, 10), sizes);
+    }
+
+    #[test]
+    fn check_of_overfull_split() {
+        let layout = Layout::merge_many(
+            vec![
+                (Layout::new_str("a"), Constraint::LENGTH(6)),
+                (Layout::new_str("b"), Constraint::LENGTH(6)),
+            ],
+            SplitDirection::HORIZONTAL,
+        );
+        let split = layout.split.as_ref().unwrap();
+        assert_eq!(
+            split.check(10, false),
+            Err(LayoutError::SPACE {
+                name: None,
+                needed: 12,
+                available: 10,
+            })
+        );
+        assert_eq!(split.check(12, false), Ok(()));
     }
 }
@@ -1,6 +1,13 @@
+#![allow(clippy::needless_return, clippy::upper_case_acronyms)]
+
 extern crate termion;
 
 pub mod border;
+pub mod buffer;
 pub mod layout;
 pub mod rect;
+pub mod resize;
+pub mod scrollbar;
+pub mod style;
+pub mod text;
 pub mod rects;
@@ -1,9 +1,12 @@
 use crate::border;
 use crate::layout;
+use crate::style::Style;
+use crate::text;
 
+use std::cmp;
 use std::collections::HashMap;
 use std::io::Write;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 
 /// A rectangle on the terminal
 #[derive(Clone)]
@@ -12,6 +15,12 @@ pub struct Rect {
     position: Position,
     has_border: bool,
     border: Option<border::Border>,
+    title: Option<border::Title>,
+    footer: Option<border::Title>,
+    junctions: Vec<(u16, u16, char)>,
+    sides: layout::Sides<bool>,
+    padding: layout::Sides<u16>,
+    style: Style,
 }
 
 /// Position information for the rectangle
@@ -26,6 +35,8 @@ struct Position {
 impl Rect {
     /// Converts a layout to a [HashMap] of [Rect].
     /// The key of the [HashMap] will be the name chosen in the [Layout.new] or similar functions.
+    /// If there is not enough space, the [Rect]s will be shrunk down to no space at all.
+    /// Use [Rect.try_from_layout] to get an error instead.
     pub fn from_layout(
         layout: &layout::Layout,
         x: u16,
@@ -33,124 +44,218 @@ impl Rect {
         width: u16,
         height: u16,
     ) -> HashMap<String, Rect> {
-        if layout.split.is_none() && layout.name.is_some() {
-            let mut hm = HashMap::new();
-            hm.insert(
-                layout.name.clone().unwrap(),
+        let mut rects = HashMap::new();
+        // Without the checks resolving the layout cannot fail.
+        let _ = Rect::resolve(layout, x, y, width, height, &mut rects, false, false);
+        Rect::join_borders(&mut rects);
+        return rects;
+    }
+
+    /// Equivalent to [Rect.from_layout], but a [LayoutError] will be returned if the layout does
+    /// not fit in the given space or is invalid. The layout is validated with [Layout.validate]
+    /// before anything is computed, the first error found will be returned.
+    pub fn try_from_layout(
+        layout: &layout::Layout,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+    ) -> Result<HashMap<String, Rect>, layout::LayoutError> {
+        if let Err(errors) = layout.validate() {
+            return Err(errors[0].clone());
+        }
+
+        let mut rects = HashMap::new();
+        Rect::resolve(layout, x, y, width, height, &mut rects, true, false)?;
+        Rect::join_borders(&mut rects);
+        return Ok(rects);
+    }
+
+    pub fn from_layout_whole(layout: &layout::Layout) -> HashMap<String, Rect> {
+        let (termwidth, termheight) = Rect::terminal_size();
+        return Rect::from_layout(layout, 1, 1, termwidth, termheight);
+    }
+
+    /// Equivalent to [Rect.from_layout_whole] but using [Rect.try_from_layout].
+    pub fn try_from_layout_whole(
+        layout: &layout::Layout,
+    ) -> Result<HashMap<String, Rect>, layout::LayoutError> {
+        let (termwidth, termheight) = Rect::terminal_size();
+        return Rect::try_from_layout(layout, 1, 1, termwidth, termheight);
+    }
+
+    /// Puts the [Rect]s of the layout into the [HashMap].
+    /// If checked, an error is returned for layouts that do not fit.
+    /// If merged, neighbouring [Layout]s with borders share the edge between them.
+    #[allow(clippy::too_many_arguments)]
+    fn resolve(
+        layout: &layout::Layout,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        rects: &mut HashMap<String, Rect>,
+        checked: bool,
+        merged: bool,
+    ) -> Result<(), layout::LayoutError> {
+        if let Some(split) = &layout.split {
+            let merged = merged || layout.merge_borders;
+            let total = match split.direction {
+                layout::SplitDirection::HORIZONTAL => width,
+                layout::SplitDirection::VERTICAL => height,
+            };
+
+            let shared = split.shared(merged);
+            let total = total.saturating_add(shared.iter().filter(|s| **s).count() as u16);
+
+            if checked {
+                split.check(total, merged)?;
+            }
+
+            let mut offset: u16 = 0;
+
+            for (((rect, _), size), shared) in split.rects.iter().zip(split.sizes(total, merged)).zip(shared) {
+                if shared {
+                    offset = offset.saturating_sub(1);
+                }
+                let (rx, ry, rw, rh) = match split.direction {
+                    layout::SplitDirection::HORIZONTAL => {
+                        (x.saturating_add(offset), y, size, height)
+                    }
+                    layout::SplitDirection::VERTICAL => (x, y.saturating_add(offset), width, size),
+                };
+                Rect::resolve(rect, rx, ry, rw, rh, rects, checked, merged)?;
+                offset = offset.saturating_add(size);
+            }
+        } else if let Some(name) = &layout.name {
+            let insets = layout.insets();
+            let inset_x = insets.west.saturating_add(insets.east);
+            let inset_y = insets.north.saturating_add(insets.south);
+
+            if checked && (width <= inset_x || height <= inset_y) {
+                let (needed, available) = if width <= inset_x {
+                    (inset_x.saturating_add(1), width)
+                } else {
+                    (inset_y.saturating_add(1), height)
+                };
+                return Err(layout::LayoutError::SPACE {
+                    name: Some(name.clone()),
+                    needed,
+                    available,
+                });
+            }
+
+            rects.insert(
+                name.clone(),
                 Rect {
-                    name: layout.name.clone().unwrap(),
+                    name: name.clone(),
                     position: Position {
-                        x: x + (layout.has_border as u16),
-                        y: y + (layout.has_border as u16),
-                        width: width - 2 * (layout.has_border as u16),
-                        height: height - 2 * (layout.has_border as u16),
+                        x: x.saturating_add(insets.west),
+                        y: y.saturating_add(insets.north),
+                        width: width.saturating_sub(inset_x),
+                        height: height.saturating_sub(inset_y),
                     },
                     has_border: layout.has_border,
                     border: layout.border.clone(),
+                    title: layout.title.clone(),
+                    footer: layout.footer.clone(),
+                    junctions: vec![],
+                    sides: layout.sides,
+                    padding: layout.padding,
+                    style: layout.style.clone(),
                 },
             );
+        }
 
-            return hm;
-        } else if layout.split.is_some() {
-            let split = layout.split.clone().unwrap();
-
-            let (r1x, r1y, r1w, r1h);
-            let (r2x, r2y, r2w, r2h);
-
-            match (split.direction, split.split_type) {
-                (layout::SplitDirection::HORIZONTAL, layout::SplitType::PERCENTAGE(percentage)) => {
-                    r1x = x;
-                    r1y = y;
-                    r1w = (width as f32 * percentage) as u16;
-                    r1h = height;
-
-                    r2x = x + r1w;
-                    r2y = y;
-                    r2w = width - r1w;
-                    r2h = height;
-                }
-
-                (layout::SplitDirection::VERTICAL, layout::SplitType::PERCENTAGE(percentage)) => {
-                    r1x = x;
-                    r1y = y;
-                    r1w = width;
-                    r1h = (height as f32 * percentage) as u16;
+        return Ok(());
+    }
 
-                    r2x = x;
-                    r2y = y + r1h;
-                    r2w = width;
-                    r2h = height - r1h;
-                }
+    /// Shows the [LayoutError] in the middle of the terminal instead of the layout.
+    /// This can be used as a fallback if the terminal is too small for the layout.
+    pub fn show_layout_error(stdout: &mut dyn Write, error: &layout::LayoutError) {
+        let (termwidth, termheight) = Rect::terminal_size();
+        let message = text::trim(&error.to_string(), termwidth as usize);
+        let x = (termwidth as usize).saturating_sub(text::width(&message)) as u16 / 2 + 1;
+        let y = termheight / 2 + 1;
 
-                (layout::SplitDirection::HORIZONTAL, layout::SplitType::VALUE(value)) => {
-                    if value >= 0 {
-                        let has_border = split.rects.0.has_border;
-                        r1x = x;
-                        r1y = y;
-                        r1w = value as u16 + 2 * (has_border as u16);
-                        r1h = height;
-
-                        r2x = x + r1w;
-                        r2y = y;
-                        r2w = width - r1w;
-                        r2h = height;
-                    } else {
-                        let has_border = split.rects.1.has_border;
-                        r1x = x;
-                        r1y = y;
-                        r1w = width - ((-value) as u16) - 2 * (has_border as u16);
-                        r1h = height;
-
-                        r2x = x + r1w;
-                        r2y = y;
-                        r2w = (-value) as u16 + 2 * (has_border as u16);
-                        r2h = height;
-                    }
-                }
+        write!(
+            stdout,
+            "{}{}{}",
+            termion::clear::All,
+            termion::cursor::Goto(x, y),
+            message
+        )
+        .unwrap();
+    }
 
-                (layout::SplitDirection::VERTICAL, layout::SplitType::VALUE(value)) => {
-                    if value >= 0 {
-                        let has_border = split.rects.0.has_border;
-                        r1x = x;
-                        r1y = y;
-                        r1w = width;
-                        r1h = value as u16 + 2 * (has_border as u16);
-
-                        r2x = x;
-                        r2y = y + r1h;
-                        r2w = width;
-                        r2h = height - r1h;
-                    } else {
-                        let has_border = split.rects.1.has_border;
-                        r1x = x;
-                        r1y = y;
-                        r1w = width;
-                        r1h = height - ((-value) as u16) - 2 * (has_border as u16);
-
-                        r2x = x;
-                        r2y = y + r1h;
-                        r2w = width;
-                        r2h = (-value) as u16 + 2 * (has_border as u16);
-                    }
-                }
+    /// Computes where the borders of the [Rect]s meet, so the junctions can be drawn with the
+    /// matching characters instead of corners and edges.
+    fn join_borders(rects: &mut HashMap<String, Rect>) {
+        let mut lines = HashMap::new();
+        for rect in rects.values() {
+            for (cell, directions) in rect.border_lines() {
+                Rect::add_lines(&mut lines, cell, directions);
             }
+        }
 
-            let mut hm1 = Rect::from_layout(&split.rects.0.clone(), r1x, r1y, r1w, r1h);
-            let hm2 = Rect::from_layout(&split.rects.1.clone(), r2x, r2y, r2w, r2h);
-
-            hm1.extend(hm2);
+        for rect in rects.values_mut() {
+            let border = match &rect.border {
+                Some(border) => border.clone(),
+                None => continue,
+            };
+            rect.junctions = rect
+                .border_lines()
+                .into_iter()
+                .filter(|(cell, directions)| lines[cell] != *directions)
+                .map(|(cell, _)| {
+                    let [n, s, e, w] = lines[&cell];
+                    return (cell.0, cell.1, border.junction(n, s, e, w));
+                })
+                .collect();
+        }
+    }
 
-            return hm1;
+    /// Adds the directions lines go from the cell, ordered north, south, east and west.
+    fn add_lines(lines: &mut HashMap<(u16, u16), [bool; 4]>, cell: (u16, u16), directions: [bool; 4]) {
+        let line = lines.entry(cell).or_insert([false; 4]);
+        for (line, direction) in line.iter_mut().zip(directions) {
+            *line |= direction;
         }
+    }
 
-        return HashMap::new();
+    /// Get the cells of the [Border] with the directions the lines go from them.
+    fn border_lines(&self) -> Vec<((u16, u16), [bool; 4])> {
+        if !self.shows_border() {
+            return vec![];
+        }
+        let sides = self.sides;
+        let (left, top, right, bottom) = self.outer();
+
+        let mut lines = HashMap::new();
+        for x in left..=right {
+            let directions = [false, false, x < right, x > left];
+            if sides.north {
+                Rect::add_lines(&mut lines, (x, top), directions);
+            }
+            if sides.south {
+                Rect::add_lines(&mut lines, (x, bottom), directions);
+            }
+        }
+        for y in top..=bottom {
+            let directions = [y > top, y < bottom, false, false];
+            if sides.west {
+                Rect::add_lines(&mut lines, (left, y), directions);
+            }
+            if sides.east {
+                Rect::add_lines(&mut lines, (right, y), directions);
+            }
+        }
+        return lines.into_iter().collect();
     }
 
-    pub fn from_layout_whole(layout: &layout::Layout) -> HashMap<String, Rect> {
-        let termsize = termion::terminal_size().ok();
-        let termwidth = termsize.map(|(w, _)| w).unwrap_or(70);
-        let termheight = termsize.map(|(_, h)| h).unwrap_or(40);
-        return Rect::from_layout(layout, 1, 1, termwidth, termheight);
+    /// Get the size of the terminal. If the size cannot be determined, 70x40 will be assumed.
+    pub(crate) fn terminal_size() -> (u16, u16) {
+        return termion::terminal_size().unwrap_or((70, 40));
     }
 
     /// Shows the writeable box of the [Rect] on the screen.
@@ -179,94 +284,137 @@ impl Rect {
 
     // Shows the [Border] of the [Rect] if existent.
     pub fn show_border(&self, stdout: &mut dyn Write) {
-        if !self.has_border || self.border.is_none() {
+        if !self.shows_border() {
             return;
         }
         let border = self.border.clone().unwrap();
-        let pos = self.position.clone();
+        let sides = self.sides;
+        let (left, top, right, bottom) = self.outer();
+        write!(stdout, "{}", border.style).unwrap();
 
-        for i in 0..(pos.height + 1) {
-            write!(
-                stdout,
-                "{}{}",
-                termion::cursor::Goto(pos.x - 1, pos.y + i),
-                border.w
-            )
-            .unwrap();
+        for y in top..=bottom {
+            if sides.west {
+                write!(stdout, "{}{}", termion::cursor::Goto(left, y), border.w).unwrap();
+            }
+            if sides.east {
+                write!(stdout, "{}{}", termion::cursor::Goto(right, y), border.e).unwrap();
+            }
+        }
 
-            write!(
-                stdout,
-                "{}{}",
-                termion::cursor::Goto(pos.x + pos.width, pos.y + i),
-                border.e
-            )
-            .unwrap();
+        for x in left..=right {
+            if sides.north {
+                write!(stdout, "{}{}", termion::cursor::Goto(x, top), border.n).unwrap();
+            }
+            if sides.south {
+                write!(stdout, "{}{}", termion::cursor::Goto(x, bottom), border.s).unwrap();
+            }
         }
 
-        for i in 0..(pos.width + 1) {
-            write!(
-                stdout,
-                "{}{}",
-                termion::cursor::Goto(pos.x + i, pos.y - 1),
-                border.n
-            )
-            .unwrap();
+        let corners = [
+            (sides.north && sides.west, left, top, border.nw),
+            (sides.north && sides.east, right, top, border.ne),
+            (sides.south && sides.west, left, bottom, border.sw),
+            (sides.south && sides.east, right, bottom, border.se),
+        ];
+        for (shown, x, y, corner) in corners {
+            if shown {
+                write!(stdout, "{}{}", termion::cursor::Goto(x, y), corner).unwrap();
+            }
+        }
 
-            write!(
-                stdout,
-                "{}{}",
-                termion::cursor::Goto(pos.x + i, pos.y + pos.height),
-                border.s
-            )
-            .unwrap();
+        for (x, y, junction) in self.junctions.iter() {
+            write!(stdout, "{}{}", termion::cursor::Goto(*x, *y), junction).unwrap();
         }
+        write!(stdout, "{}", border.style.reset()).unwrap();
 
+        if let (Some(title), true) = (&self.title, sides.north) {
+            self.show_title(stdout, title, top);
+        }
+        if let (Some(footer), true) = (&self.footer, sides.south) {
+            self.show_title(stdout, footer, bottom);
+        }
+    }
+
+    /// Get the columns and rows of the edges of the [Rect] including its border, ordered
+    /// (left, top, right, bottom).
+    pub(crate) fn outer(&self) -> (u16, u16, u16, u16) {
+        let pos = &self.position;
+        let padding = &self.padding;
+        let border = |side: bool| (self.shows_border() && side) as u16;
+        return (
+            pos.x.saturating_sub(padding.west.saturating_add(border(self.sides.west))),
+            pos.y.saturating_sub(padding.north.saturating_add(border(self.sides.north))),
+            pos.x.saturating_add(pos.width).saturating_add(padding.east).saturating_sub(1 - border(self.sides.east)),
+            pos.y.saturating_add(pos.height).saturating_add(padding.south).saturating_sub(1 - border(self.sides.south)),
+        );
+    }
+
+    /// Shows the [Title] in the given row of the terminal, between the corners of the [Border].
+    fn show_title(&self, stdout: &mut dyn Write, title: &border::Title, y: u16) {
+        let x = self.position.x.saturating_sub(self.padding.west);
+        let width = self.position.width as usize + self.padding.west as usize + self.padding.east as usize;
+        let text = text::trim(&title.text, width.saturating_sub(2));
+        if text.is_empty() {
+            return;
+        }
+
+        let label = format!(" {} ", text);
+        let space = width - text::width(&label);
+        let offset = match title.alignment {
+            text::Alignment::LEFT => 0,
+            text::Alignment::CENTER => space / 2,
+            text::Alignment::RIGHT => space,
+        };
         write!(
             stdout,
-            "{}{}",
-            termion::cursor::Goto(pos.x - 1, pos.y - 1),
-            border.nw
-        )
-        .unwrap();
-        write!(
-            stdout,
-            "{}{}",
-            termion::cursor::Goto(pos.x + pos.width, pos.y - 1),
-            border.ne
-        )
-        .unwrap();
-        write!(
-            stdout,
-            "{}{}",
-            termion::cursor::Goto(pos.x - 1, pos.y + pos.height),
-            border.sw
-        )
-        .unwrap();
-        write!(
-            stdout,
-            "{}{}",
-            termion::cursor::Goto(pos.x + pos.width, pos.y + pos.height),
-            border.se
+            "{}{}{}{}",
+            termion::cursor::Goto(x + offset as u16, y),
+            title.style,
+            label,
+            title.style.reset()
         )
         .unwrap();
     }
 
+    /// Changes the [Style] the [Border] is written with, e.g. to highlight the focused section.
+    pub fn set_border_style(&mut self, style: Style) {
+        if let Some(border) = &mut self.border {
+            border.style = style;
+        }
+    }
+
+    /// Sets the [Title] shown in the top edge of the [Border]. None to remove it.
+    pub fn set_title(&mut self, title: Option<border::Title>) {
+        self.title = title;
+    }
+
+    /// Sets the [Title] shown in the bottom edge of the [Border]. None to remove it.
+    pub fn set_footer(&mut self, footer: Option<border::Title>) {
+        self.footer = footer;
+    }
+
+    /// Sets the default [Style] of the [Rect]. Everything written without own colors uses it and
+    /// [Rect.clear] fills the [Rect] with its background.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Get the default [Style] of the [Rect].
+    pub fn get_style(&self) -> &Style {
+        return &self.style;
+    }
+
     /// Write a string to the screen at the given positions.
+    /// The string is written with the [Style] of the [Rect].
     /// The positions are (0,0)-based.
     /// If the string is to long to fit in the line it will be wrapped.
     /// If the string will not fit in the rect a [Error] will be returned and nothing will be written.
     pub fn write(&self, stdout: &mut dyn Write, str: &str, x: u16, y: u16) -> Result<(), Error> {
-        return self.write_colored(
-            stdout,
-            str,
-            x,
-            y,
-            &termion::color::Reset,
-            &termion::color::Reset,
-        );
+        return self.write_styled(stdout, str, x, y, &Style::new());
     }
 
     /// Equivalent to [Rect.write] but with color.
+    /// [termion::color::Reset] keeps the color of the [Style] of the [Rect].
     pub fn write_colored(
         &self,
         stdout: &mut dyn Write,
@@ -275,34 +423,103 @@ impl Rect {
         y: u16,
         fg_color: &dyn termion::color::Color,
         bg_color: &dyn termion::color::Color,
+    ) -> Result<(), Error> {
+        return self.write_styled(stdout, str, x, y, &Style::from_colors(fg_color, bg_color));
+    }
+
+    /// Equivalent to [Rect.write] but with a [Style]. Everything the [Style] does not set is
+    /// taken from the [Style] of the [Rect].
+    pub fn write_styled(
+        &self,
+        stdout: &mut dyn Write,
+        str: &str,
+        x: u16,
+        y: u16,
+        style: &Style,
+    ) -> Result<(), Error> {
+        return self.write_styled_wrapped(stdout, str, x, y, text::WrapMode::CHARACTER, style);
+    }
+
+    /// Equivalent to [Rect.write] but the string is wrapped as given by the [WrapMode].
+    pub fn write_wrapped(
+        &self,
+        stdout: &mut dyn Write,
+        str: &str,
+        x: u16,
+        y: u16,
+        mode: text::WrapMode,
+    ) -> Result<(), Error> {
+        return self.write_styled_wrapped(stdout, str, x, y, mode, &Style::new());
+    }
+
+    /// Equivalent to [Rect.write_wrapped] but with color.
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_colored_wrapped(
+        &self,
+        stdout: &mut dyn Write,
+        str: &str,
+        x: u16,
+        y: u16,
+        mode: text::WrapMode,
+        fg_color: &dyn termion::color::Color,
+        bg_color: &dyn termion::color::Color,
+    ) -> Result<(), Error> {
+        return self.write_styled_wrapped(stdout, str, x, y, mode, &Style::from_colors(fg_color, bg_color));
+    }
+
+    /// Equivalent to [Rect.write_wrapped] but with a [Style].
+    pub fn write_styled_wrapped(
+        &self,
+        stdout: &mut dyn Write,
+        str: &str,
+        x: u16,
+        y: u16,
+        mode: text::WrapMode,
+        style: &Style,
     ) -> Result<(), Error> {
         self.show_border(stdout);
-        if y > self.position.height {
-            return Err(Error::new(ErrorKind::Other, "Position out of bounds"));
+        if str.is_empty() {
+            return Ok(());
         }
 
-        let overflow = (x as i16) + (str.len() as i16) - (self.position.width as i16);
+        let lines = text::wrap(
+            str,
+            (self.position.width as usize).saturating_sub(x as usize),
+            self.position.width as usize,
+            mode,
+        );
+        let style = self.style.patch(style);
+
+        for (i, line) in lines.iter().enumerate() {
+            let line_y = y as usize + i;
+            if line_y >= self.position.height as usize {
+                return Err(Error::other("Position out of bounds"));
+            }
+            let line_x = if i == 0 { x } else { 0 };
 
-        // Overflow to next line
-        if overflow > 0 {
-            let thisline = &str[..(str.len() - overflow as usize)];
-            let nextline = &str[(str.len() - overflow as usize)..];
-            self.write_colored(stdout, thisline, x, y, fg_color, bg_color)
-                .unwrap();
-            return self.write_colored(stdout, nextline, 0, y + 1, fg_color, bg_color);
+            write!(
+                stdout,
+                "{}{}{}{}",
+                style,
+                termion::cursor::Goto(self.position.x + line_x, self.position.y + line_y as u16),
+                line,
+                style.reset(),
+            )?;
         }
 
-        // No overflow
-        return write!(
-            stdout,
-            "{}{}{}{}{}{}",
-            termion::color::Fg(fg_color),
-            termion::color::Bg(bg_color),
-            termion::cursor::Goto(self.position.x + x, self.position.y + y),
+        return Ok(());
+    }
+
+    /// Get the amount of lines the string needs when written at the given column with the
+    /// given [WrapMode].
+    pub fn line_count(&self, str: &str, x: u16, mode: text::WrapMode) -> usize {
+        return text::wrap(
             str,
-            termion::color::Fg(termion::color::Reset),
-            termion::color::Bg(termion::color::Reset),
-        );
+            (self.position.width as usize).saturating_sub(x as usize),
+            self.position.width as usize,
+            mode,
+        )
+        .len();
     }
 
     /// Equivalent to write, but when the line cannot hold the whole string, the rest will be
@@ -314,7 +531,7 @@ impl Rect {
         x: u16,
         y: u16,
     ) -> Result<(), Error> {
-       return self.write_colored_trimmed(stdout, str, x, y, &termion::color::Reset, &termion::color::Reset);
+       return self.write_styled_trimmed(stdout, str, x, y, &Style::new());
     }
 
     /// Equivalent to write_trimmed but with colors.
@@ -327,49 +544,117 @@ impl Rect {
         fg_color: &dyn termion::color::Color,
         bg_color: &dyn termion::color::Color,
     ) -> Result<(), Error> {
+        return self.write_styled_trimmed(stdout, str, x, y, &Style::from_colors(fg_color, bg_color));
+    }
+
+    /// Equivalent to write_trimmed but with a [Style].
+    pub fn write_styled_trimmed(
+        &self,
+        stdout: &mut dyn Write,
+        str: &str,
+        x: u16,
+        y: u16,
+        style: &Style,
+    ) -> Result<(), Error> {
 
         // Replace tabs
         let str = &String::from(str).replace('\t', "    ");
 
         self.show_border(stdout);
-        if y > self.position.height {
-            return Err(Error::new(ErrorKind::Other, "Position out of bounds"));
+        if y >= self.position.height {
+            return Err(Error::other("Position out of bounds"));
         }
 
-        let overflow = (x as isize) + (str.len() as isize) - (self.position.width as isize);
-
-        // Overflow, the string must be shortened.
-        if overflow > 0 {
-            let shortened = &str[..((str.len() - overflow as usize) - 3)];
-            return write!(
-                stdout,
-                "{}{}...",
-                termion::cursor::Goto(self.position.x + x, self.position.y + y),
-                shortened
-            );
-        }
+        // The string is shortened if it overflows.
+        let available = (self.position.width as usize).saturating_sub(x as usize);
+        let str = text::trim(str, available);
 
-        // No overflow
+        let style = self.style.patch(style);
         return write!(
             stdout,
-            "{}{}{}{}{}{}",
-            termion::color::Fg(fg_color),
-            termion::color::Bg(bg_color),
+            "{}{}{}{}",
+            style,
             termion::cursor::Goto(self.position.x + x, self.position.y + y),
             str,
-            termion::color::Fg(termion::color::Reset),
-            termion::color::Bg(termion::color::Reset)
+            style.reset()
         );
     }
 
     /// Write a single line and fill the rest with spaces
     pub fn write_colored_trimmed_line(&self, stdout: &mut dyn Write, str: &str, y: u16, fg_color: &dyn termion::color::Color, bg_color: &dyn termion::color::Color) -> Result<(), Error> {
-        self.write_colored_trimmed(stdout, str, 0, y, fg_color, bg_color)?;
-        if str.len() < self.position.width as usize {
-            self.write(stdout, &" ".repeat(self.position.width as usize - str.len()), str.len() as u16, y)?;
+        return self.write_styled_trimmed_parts(stdout, &[(str, &Style::from_colors(fg_color, bg_color))], y);
+    }
+
+    /// Equivalent to [Rect.write_colored_trimmed_line] but with a [Style].
+    pub fn write_styled_trimmed_line(&self, stdout: &mut dyn Write, str: &str, y: u16, style: &Style) -> Result<(), Error> {
+        return self.write_styled_trimmed_parts(stdout, &[(str, style)], y);
+    }
+
+    /// Equivalent to [Rect.write_colored_trimmed_line], but the line consists of several parts,
+    /// each with its own foreground and background color.
+    /// If the line has to be shortened, the "..." gets the colors of the part that is cut.
+    pub fn write_colored_trimmed_parts(
+        &self,
+        stdout: &mut dyn Write,
+        parts: &[(&str, &dyn termion::color::Color, &dyn termion::color::Color)],
+        y: u16,
+    ) -> Result<(), Error> {
+        let styles: Vec<Style> = parts
+            .iter()
+            .map(|(_, fg, bg)| Style::from_colors(*fg, *bg))
+            .collect();
+        let parts: Vec<(&str, &Style)> = parts
+            .iter()
+            .zip(styles.iter())
+            .map(|((str, _, _), style)| (*str, style))
+            .collect();
+        return self.write_styled_trimmed_parts(stdout, &parts, y);
+    }
+
+    /// Equivalent to [Rect.write_colored_trimmed_parts] but each part has a [Style].
+    pub fn write_styled_trimmed_parts(
+        &self,
+        stdout: &mut dyn Write,
+        parts: &[(&str, &Style)],
+        y: u16,
+    ) -> Result<(), Error> {
+        let parts: Vec<(String, &Style)> = parts
+            .iter()
+            .map(|(str, style)| (str.replace('\t', "    "), *style))
+            .collect();
+        let width = self.position.width as usize;
+        let fits = parts.iter().map(|(str, _)| text::width(str)).sum::<usize>() <= width;
+        let available = if fits { width } else { width.saturating_sub(3) };
+
+        let mut x = 0;
+        for (str, style) in parts.iter() {
+            let (shown, cut) = text::split_at_width(str, available - x);
+            self.write_styled_trimmed(stdout, shown, x as u16, y, style)?;
+            x += text::width(shown);
+
+            if !cut.is_empty() {
+                let dots = ".".repeat(cmp::min(3, width - x));
+                self.write_styled_trimmed(stdout, &dots, x as u16, y, style)?;
+                x += dots.len();
+                break;
+            }
         }
-        Ok(())
 
+        if x < width {
+            self.write(stdout, &" ".repeat(width - x), x as u16, y)?;
+        }
+        return Ok(());
+    }
+
+    /// Moves the cursor of the terminal to the given position in the [Rect].
+    /// The positions are (0,0)-based.
+    pub fn set_cursor(&self, stdout: &mut dyn Write, x: u16, y: u16) {
+        write!(
+            stdout,
+            "{}",
+            termion::cursor::Goto(self.position.x + x, self.position.y + y)
+        )
+        .unwrap();
     }
 
     /// Get the dimensions of the [Rect]. The tupel consists is ordered (width, height).
@@ -377,14 +662,42 @@ impl Rect {
         return (self.position.width, self.position.height);
     }
 
-    /// Clears the entire rectangle.
+    /// Get the position of the top left corner of the writeable box in the terminal.
+    /// The position is (1,1)-based like [termion::cursor::Goto].
+    pub(crate) fn get_origin(&self) -> (u16, u16) {
+        return (self.position.x, self.position.y);
+    }
+
+    /// Whether a [Border] is drawn around the [Rect].
+    pub(crate) fn shows_border(&self) -> bool {
+        return self.has_border && self.border.is_some();
+    }
+
+    /// Get the sides the [Border] is drawn on, if it is shown at all.
+    pub(crate) fn get_sides(&self) -> layout::Sides<bool> {
+        return self.sides;
+    }
+
+    /// A copy of the [Rect] with the given amount of columns removed on the right.
+    pub(crate) fn without_columns(&self, columns: u16) -> Rect {
+        let mut rect = self.clone();
+        rect.position.width = rect.position.width.saturating_sub(columns);
+        return rect;
+    }
+
+    /// Clears the entire rectangle including its padding. The background of the [Style] of the
+    /// [Rect] is used.
     pub fn clear(&self, stdout: &mut dyn Write) {
-        self.write(
-            stdout,
-            &" ".repeat((self.position.width * self.position.height) as usize),
-            0,
-            0,
-        )
-        .unwrap();
+        self.show_border(stdout);
+
+        let pos = &self.position;
+        let padding = &self.padding;
+        let x = pos.x.saturating_sub(padding.west);
+        let blank = " ".repeat(pos.width as usize + padding.west as usize + padding.east as usize);
+        write!(stdout, "{}", self.style).unwrap();
+        for y in pos.y.saturating_sub(padding.north)..pos.y.saturating_add(pos.height).saturating_add(padding.south) {
+            write!(stdout, "{}{}", termion::cursor::Goto(x, y), blank).unwrap();
+        }
+        write!(stdout, "{}", self.style.reset()).unwrap();
     }
 }
@@ -0,0 +1,65 @@
+use std::cmp;
+
+/// The selected row and the first shown row of a widget that scrolls through rows, e.g. a
+/// [TableRect] or a [TreeRect].
+/// The methods take the amount of rows and the amount of rows that can be shown.
+pub(crate) struct Cursor {
+    pub(crate) selected: usize,
+    pub(crate) scroll: usize,
+}
+
+impl Cursor {
+    /// Create a new [Cursor] on the first row.
+    pub(crate) fn new() -> Cursor {
+        return Cursor {
+            selected: 0,
+            scroll: 0,
+        };
+    }
+
+    /// Scrolls as little as possible to show the selected row.
+    pub(crate) fn scroll_to_selected(&mut self, len: usize, height: usize) {
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if height > 0 && self.selected >= self.scroll + height {
+            self.scroll = self.selected + 1 - height;
+        }
+        self.scroll = cmp::min(self.scroll, len.saturating_sub(height));
+    }
+
+    /// Moves the cursor to the next row. Stops at the last row.
+    pub(crate) fn next(&mut self, len: usize, height: usize) {
+        self.selected = cmp::min(self.selected + 1, len.saturating_sub(1));
+        self.scroll_to_selected(len, height);
+    }
+
+    /// Moves the cursor to the previous row. Stops at the first row.
+    pub(crate) fn prev(&mut self, len: usize, height: usize) {
+        self.selected = self.selected.saturating_sub(1);
+        self.scroll_to_selected(len, height);
+    }
+
+    /// Moves the cursor down by the amount of rows shown. Stops at the last row.
+    pub(crate) fn page_down(&mut self, len: usize, height: usize) {
+        self.selected = cmp::min(self.selected + cmp::max(height, 1), len.saturating_sub(1));
+        self.scroll_to_selected(len, height);
+    }
+
+    /// Moves the cursor up by the amount of rows shown. Stops at the first row.
+    pub(crate) fn page_up(&mut self, len: usize, height: usize) {
+        self.selected = self.selected.saturating_sub(cmp::max(height, 1));
+        self.scroll_to_selected(len, height);
+    }
+
+    /// Moves the cursor to the first row.
+    pub(crate) fn first(&mut self, len: usize, height: usize) {
+        self.selected = 0;
+        self.scroll_to_selected(len, height);
+    }
+
+    /// Moves the cursor to the last row.
+    pub(crate) fn last(&mut self, len: usize, height: usize) {
+        self.selected = len.saturating_sub(1);
+        self.scroll_to_selected(len, height);
+    }
+}
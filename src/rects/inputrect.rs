@@ -0,0 +1,247 @@
+use crate::rect::Rect;
+use crate::text;
+
+use std::io::Write;
+use termion::event::Key;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Uses a rect to make a single line input field.
+/// If the text is longer than the rect, it will be scrolled horizontally to keep the cursor visible.
+pub struct InputRect {
+    rect: Rect,
+    text: String,
+    cursor: usize,
+    scroll: usize,
+    placeholder: String,
+    max_length: Option<usize>,
+    mask: Option<char>,
+}
+
+impl InputRect {
+    /// Clears the rect
+    pub fn clear(&self, stdout: &mut dyn Write) {
+        self.rect.clear(stdout);
+    }
+
+    /// Replaces the rect the input is shown in, e.g. after the terminal was resized.
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.scroll_to_cursor();
+    }
+
+    /// Get the text that was entered.
+    pub fn get_text(&self) -> String {
+        return self.text.clone();
+    }
+
+    /// Set the text. The cursor will be moved to the end.
+    /// If the text is longer than the maximum length, it will be cut.
+    pub fn set_text(&mut self, text: &str) {
+        let graphemes = self.max_length.unwrap_or(usize::MAX);
+        self.text = text.graphemes(true).take(graphemes).collect();
+        self.scroll = 0;
+        self.end();
+    }
+
+    /// Set the placeholder that is shown while there is no text.
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = String::from(placeholder);
+    }
+
+    /// Set the maximum amount of characters that can be entered. None for no limit.
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
+        if let Some(max_length) = max_length {
+            self.text = self.text.graphemes(true).take(max_length).collect();
+            self.cursor = std::cmp::min(self.cursor, max_length);
+            self.scroll_to_cursor();
+        }
+    }
+
+    /// Set the character that is shown instead of every character, e.g. for passwords.
+    /// None to show the text.
+    pub fn set_mask(&mut self, mask: Option<char>) {
+        self.mask = mask;
+        self.scroll_to_cursor();
+    }
+
+    /// Get the position of the cursor, counted in characters.
+    pub fn get_cursor(&self) -> usize {
+        return self.cursor;
+    }
+
+    /// Inserts a character at the cursor. Control characters are ignored.
+    pub fn insert(&mut self, c: char) {
+        if c.is_control() {
+            return;
+        }
+        // Characters without width are combined with the previous one.
+        let combining = text::width(&c.to_string()) == 0;
+        if !combining && self.max_length.is_some_and(|max| self.len() >= max) {
+            return;
+        }
+
+        let index = self.byte_index(self.cursor);
+        self.text.insert(index, c);
+        if !combining {
+            self.cursor += 1;
+        }
+        self.scroll_to_cursor();
+    }
+
+    /// Deletes the character before the cursor.
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        self.delete();
+    }
+
+    /// Deletes the character at the cursor.
+    pub fn delete(&mut self) {
+        if self.cursor >= self.len() {
+            return;
+        }
+        let range = self.byte_index(self.cursor)..self.byte_index(self.cursor + 1);
+        self.text.replace_range(range, "");
+        self.scroll_to_cursor();
+    }
+
+    /// Moves the cursor one character to the left.
+    pub fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+        self.scroll_to_cursor();
+    }
+
+    /// Moves the cursor one character to the right.
+    pub fn right(&mut self) {
+        self.cursor = std::cmp::min(self.cursor + 1, self.len());
+        self.scroll_to_cursor();
+    }
+
+    /// Moves the cursor to the start of the text.
+    pub fn home(&mut self) {
+        self.cursor = 0;
+        self.scroll_to_cursor();
+    }
+
+    /// Moves the cursor to the end of the text.
+    pub fn end(&mut self) {
+        self.cursor = self.len();
+        self.scroll_to_cursor();
+    }
+
+    /// Edits the text according to the key. Returns whether the key was used.
+    pub fn handle_key(&mut self, key: Key) -> bool {
+        match key {
+            Key::Char(c) if !c.is_control() => self.insert(c),
+            Key::Backspace => self.backspace(),
+            Key::Delete => self.delete(),
+            Key::Left => self.left(),
+            Key::Right => self.right(),
+            Key::Home | Key::Ctrl('a') => self.home(),
+            Key::End | Key::Ctrl('e') => self.end(),
+            _ => return false,
+        }
+        return true;
+    }
+
+    /// Show the input field and move the cursor of the terminal to the cursor of the input.
+    pub fn show(&self, stdout: &mut dyn Write) {
+        self.rect.show_border(stdout);
+        if self.rect.get_dimensions().1 == 0 {
+            return;
+        }
+        let width = self.rect.get_dimensions().0 as usize;
+
+        if self.text.is_empty() {
+            let placeholder = text::split_at_width(&self.placeholder, width).0;
+            self.rect
+                .write_colored(
+                    stdout,
+                    &format!("{}{}", placeholder, " ".repeat(width - text::width(placeholder))),
+                    0,
+                    0,
+                    &termion::color::LightBlack,
+                    &termion::color::Reset,
+                )
+                .unwrap();
+            self.rect.set_cursor(stdout, 0, 0);
+            return;
+        }
+
+        let visible: String = self.displayed().skip(self.scroll).collect();
+        let visible = text::split_at_width(&visible, width).0;
+        self.rect
+            .write(
+                stdout,
+                &format!("{}{}", visible, " ".repeat(width - text::width(visible))),
+                0,
+                0,
+            )
+            .unwrap();
+
+        let column = self.column(self.scroll, self.cursor);
+        self.rect.set_cursor(stdout, column as u16, 0);
+    }
+
+    /// The amount of characters in the text.
+    fn len(&self) -> usize {
+        return text::grapheme_count(&self.text);
+    }
+
+    /// Get the byte index of the character with the given index.
+    fn byte_index(&self, index: usize) -> usize {
+        return text::byte_index(&self.text, index);
+    }
+
+    /// The characters as they are shown, i.e. masked if there is a mask.
+    fn displayed(&self) -> impl Iterator<Item = String> + '_ {
+        return self.text.graphemes(true).map(move |g| match self.mask {
+            Some(mask) => mask.to_string(),
+            None => String::from(g),
+        });
+    }
+
+    /// The amount of columns the characters from `from` to `to` take up.
+    fn column(&self, from: usize, to: usize) -> usize {
+        return self
+            .displayed()
+            .skip(from)
+            .take(to.saturating_sub(from))
+            .map(|g| text::width(&g))
+            .sum();
+    }
+
+    /// Scrolls such that the cursor is visible.
+    fn scroll_to_cursor(&mut self) {
+        let width = self.rect.get_dimensions().0 as usize;
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        }
+        // Keep one column free for the cursor.
+        while self.scroll < self.cursor && self.column(self.scroll, self.cursor) >= width {
+            self.scroll += 1;
+        }
+        // Do not leave space at the end if more text fits in front.
+        while self.scroll > 0 && self.column(self.scroll - 1, self.len()) < width {
+            self.scroll -= 1;
+        }
+    }
+}
+
+impl crate::rect::Rect {
+    /// Creates an input field from the rect.
+    pub fn into_input(self) -> Box<InputRect> {
+        return Box::new(InputRect {
+            rect: self,
+            text: String::new(),
+            cursor: 0,
+            scroll: 0,
+            placeholder: String::new(),
+            max_length: None,
+            mask: None,
+        });
+    }
+}
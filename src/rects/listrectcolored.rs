@@ -1,31 +1,109 @@
 use crate::rect::Rect;
+use crate::scrollbar::Scrollbar;
+use crate::style::Style;
 use std::io::Write;
 use std::cmp;
+use termion::event::Key;
 
 /// Uses a rect to make a list of items. These items can be selected.
+/// In the multi selection mode, several items can be marked in addition to the selected one.
+/// A filter can be set to only show the items matching it, see [ListRectColored.set_filter].
+/// The items can be enumerated or prefixed, see [Enumeration].
 pub struct ListRectColored<'a, T> {
     rect: Rect,
     elements: Vec<(T, String)>,
     selected: usize,
-    theme: &'a dyn Fn(
-        T,
-        bool,
-    ) -> (
-        &'static dyn termion::color::Color,
-        &'static dyn termion::color::Color,
-    ),
+    theme: Theme<'a, T>,
+    multi_select: bool,
+    marked: Vec<bool>,
+    mark_anchor: Option<usize>,
+    marked_theme: Option<MarkedTheme<'a, T>>,
+    style_theme: Option<StyleTheme<'a, T>>,
+    filter: String,
+    filter_mode: FilterMode,
+    visible: Vec<usize>,
+    matches: Vec<Vec<usize>>,
+    highlight: &'static dyn termion::color::Color,
+    enumeration: Enumeration<'a, T>,
+    wrap_around: bool,
+    scroll: usize,
+    scrolloff: usize,
+    scrollbar: Option<Scrollbar>,
 }
 
+/// The prefix shown in front of each item of a [ListRectColored].
+/// Items keep their number when the list is filtered.
+pub enum Enumeration<'a, T> {
+    /// No prefix.
+    NONE,
+    /// "1. ", "2. ", ... The numbers are aligned to the right.
+    NUMBERS,
+    /// "a. ", "b. ", ..., "z. ", "aa. ", ... The letters are aligned to the right.
+    LETTERS,
+    /// "I. ", "II. ", ... The numerals are aligned to the right.
+    ROMAN,
+    /// "• " in front of every item.
+    BULLETS,
+    /// "[x] " in front of marked items, "[ ] " in front of the others.
+    CHECKBOXES,
+    /// The prefix is given by a function taking the index of the item and its extra information.
+    CUSTOM(&'a dyn Fn(usize, T) -> String),
+}
+
+/// How the filter of a [ListRectColored] is matched against the items.
+#[derive(Clone, Copy, PartialEq)]
+pub enum FilterMode {
+    /// Items containing the filter, ignoring the case.
+    SUBSTRING,
+    /// Items containing all characters of the filter in order, ignoring the case.
+    /// The best matches are shown first.
+    FUZZY,
+}
+
+/// A theme taking the extra information of an item and whether it is selected and returning the
+/// foreground color and background color, see [ListRectColored.set_theme].
+pub type Theme<'a, T> = &'a dyn Fn(
+    T,
+    bool,
+) -> (
+    &'static dyn termion::color::Color,
+    &'static dyn termion::color::Color,
+);
+
+/// The theme of the multi selection mode, see [ListRectColored.set_marked_theme].
+pub type MarkedTheme<'a, T> = &'a dyn Fn(
+    T,
+    bool,
+    bool,
+) -> (
+    &'static dyn termion::color::Color,
+    &'static dyn termion::color::Color,
+);
+
+/// A theme returning a [Style], see [ListRectColored.set_style_theme].
+pub type StyleTheme<'a, T> = &'a dyn Fn(T, bool, bool) -> Style;
+
 impl<'a, T: Clone> ListRectColored<'a, T> {
     /// Clears the rect
     pub fn clear(&self, stdout: &mut dyn Write) {
         self.rect.clear(stdout);
     }
 
+    /// Replaces the rect the list is shown in, e.g. after the terminal was resized.
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.scroll_to_selected();
+    }
+
     /// Set the elements
     pub fn set_elements(&mut self, elements: Vec<(T, String)>) {
+        self.marked = vec![false; elements.len()];
+        self.mark_anchor = None;
         self.elements = elements;
+        self.visible = vec![];
         self.selected = 0;
+        self.scroll = 0;
+        self.refilter();
     }
 
     /// Change one single element in the list
@@ -34,95 +112,548 @@ impl<'a, T: Clone> ListRectColored<'a, T> {
             return;
         }
         self.elements[index] = element;
+        self.refilter();
     }
 
     /// Stets the theme.
     /// The theme is a function that takes the extra information given with each item and wheather it is selected
     /// and returns the foreground color and background color
-    pub fn set_theme(
-        &mut self,
-        theme: &'a dyn Fn(
-            T,
-            bool,
-        ) -> (
-            &'static dyn termion::color::Color,
-            &'static dyn termion::color::Color,
-        ),
-    ) {
+    pub fn set_theme(&mut self, theme: Theme<'a, T>) {
         self.theme = theme;
     }
 
+    /// Enables or disables the multi selection mode. Disabling it unmarks all items.
+    pub fn set_multi_select(&mut self, multi_select: bool) {
+        self.multi_select = multi_select;
+        if !multi_select {
+            self.unmark_all();
+        }
+    }
+
+    /// Sets the theme used in the multi selection mode.
+    /// The theme is a function that takes the extra information given with each item, whether
+    /// the cursor is on it and whether it is marked and returns the foreground color and
+    /// background color.
+    /// Without this theme, marked items are shown like the selected one.
+    pub fn set_marked_theme(&mut self, theme: MarkedTheme<'a, T>) {
+        self.marked_theme = Some(theme);
+    }
+
+    /// Sets a theme that returns a [Style], so items can also be e.g. bold or underlined.
+    /// The theme is a function that takes the extra information given with each item, whether
+    /// the cursor is on it and whether it is marked.
+    /// This theme is used instead of the other themes.
+    pub fn set_style_theme(&mut self, theme: StyleTheme<'a, T>) {
+        self.style_theme = Some(theme);
+    }
+
+    /// Sets the filter. Only the items matching it will be shown, see [FilterMode].
+    /// The selected item stays selected if it still matches, otherwise the first item is selected.
+    /// An empty filter shows all items.
+    pub fn set_filter(&mut self, filter: &str) {
+        self.filter = String::from(filter);
+        self.refilter();
+    }
+
+    /// Get the current filter.
+    pub fn get_filter(&self) -> &str {
+        return &self.filter;
+    }
+
+    /// Sets how the filter is matched against the items. The default is [FilterMode::SUBSTRING].
+    pub fn set_filter_mode(&mut self, mode: FilterMode) {
+        self.filter_mode = mode;
+        self.refilter();
+    }
+
+    /// Sets the foreground color of the characters matching the filter. The default is yellow.
+    pub fn set_highlight_color(&mut self, color: &'static dyn termion::color::Color) {
+        self.highlight = color;
+    }
+
+    /// Sets the prefix shown in front of each item. The default is [Enumeration::NONE].
+    pub fn set_enumeration(&mut self, enumeration: Enumeration<'a, T>) {
+        self.enumeration = enumeration;
+    }
+
+    /// Get the prefix of the item with the given index, without the alignment.
+    fn prefix(&self, index: usize, marked: bool) -> String {
+        return match self.enumeration {
+            Enumeration::NONE => String::new(),
+            Enumeration::NUMBERS => format!("{}. ", index + 1),
+            Enumeration::LETTERS => format!("{}. ", letters(index + 1)),
+            Enumeration::ROMAN => format!("{}. ", roman(index + 1)),
+            Enumeration::BULLETS => String::from("• "),
+            Enumeration::CHECKBOXES => String::from(if marked { "[x] " } else { "[ ] " }),
+            Enumeration::CUSTOM(prefix) => prefix(index, self.elements[index].0.clone()),
+        };
+    }
+
+    /// Get the width all prefixes are aligned to.
+    fn prefix_width(&self) -> usize {
+        return match self.enumeration {
+            Enumeration::NUMBERS | Enumeration::LETTERS => {
+                self.prefix(self.elements.len().saturating_sub(1), false).len()
+            }
+            Enumeration::ROMAN => (0..self.elements.len())
+                .map(|index| self.prefix(index, false).len())
+                .max()
+                .unwrap_or(0),
+            _ => 0,
+        };
+    }
+
+    /// Get the number of items matching the filter.
+    pub fn get_visible_count(&self) -> usize {
+        return self.visible.len();
+    }
+
+    /// Computes the visible items for the current filter.
+    fn refilter(&mut self) {
+        let selected = self.visible.get(self.selected).copied();
+
+        let filter: Vec<char> = self.filter.chars().map(fold).collect();
+        let mut visible: Vec<(usize, i32, Vec<usize>)> = self
+            .elements
+            .iter()
+            .enumerate()
+            .filter_map(|(index, element)| {
+                let chars: Vec<char> = element.1.chars().map(fold).collect();
+                let found = match self.filter_mode {
+                    FilterMode::SUBSTRING => substring_match(&chars, &filter),
+                    FilterMode::FUZZY => fuzzy_match(&chars, &filter),
+                };
+                return found.map(|(score, positions)| (index, score, positions));
+            })
+            .collect();
+        if self.filter_mode == FilterMode::FUZZY {
+            visible.sort_by_key(|(_, score, _)| cmp::Reverse(*score));
+        }
+
+        self.visible = visible.iter().map(|(index, _, _)| *index).collect();
+        self.matches = visible.into_iter().map(|(_, _, positions)| positions).collect();
+        let selected = selected
+            .and_then(|selected| self.visible.iter().position(|index| *index == selected))
+            .unwrap_or(0);
+        self.select_centered(selected);
+    }
+
+    /// Marks the selected item or unmarks it if it is already marked.
+    /// Only possible in the multi selection mode.
+    pub fn toggle_mark(&mut self) {
+        if !self.multi_select || self.selected >= self.visible.len() {
+            return;
+        }
+        let index = self.visible[self.selected];
+        self.marked[index] = !self.marked[index];
+        self.mark_anchor = Some(index);
+    }
+
+    /// Marks all visible items from the last toggled item to the selected one.
+    /// Only possible in the multi selection mode.
+    pub fn mark_range(&mut self) {
+        if !self.multi_select || self.selected >= self.visible.len() {
+            return;
+        }
+        let anchor = self
+            .mark_anchor
+            .and_then(|anchor| self.visible.iter().position(|index| *index == anchor))
+            .unwrap_or(self.selected);
+        let (from, to) = (cmp::min(anchor, self.selected), cmp::max(anchor, self.selected));
+        for index in self.visible[from..=to].iter() {
+            self.marked[*index] = true;
+        }
+        self.mark_anchor = Some(self.visible[self.selected]);
+    }
+
+    /// Marks all items matching the filter. Only possible in the multi selection mode.
+    pub fn mark_all(&mut self) {
+        if !self.multi_select {
+            return;
+        }
+        for index in self.visible.iter() {
+            self.marked[*index] = true;
+        }
+    }
+
+    /// Unmarks all items.
+    pub fn unmark_all(&mut self) {
+        self.marked = vec![false; self.elements.len()];
+        self.mark_anchor = None;
+    }
+
+    /// Get the extra information of all marked items.
+    pub fn get_marked(&self) -> Vec<T> {
+        return self
+            .elements
+            .iter()
+            .zip(self.marked.iter())
+            .filter(|(_, marked)| **marked)
+            .map(|(element, _)| element.0.clone())
+            .collect();
+    }
+
+    /// Get the indices of all marked items.
+    pub fn get_marked_indices(&self) -> Vec<usize> {
+        return (0..self.elements.len())
+            .filter(|index| self.marked[*index])
+            .collect();
+    }
+
     /// Get the string of the currently selected item.
     pub fn get_selected(&self) -> Option<String> {
-        if self.elements.len() == 0 || self.selected >= self.elements.len() {
-            return None;
-        }
-        return Some(self.elements[self.selected].1.clone());
+        let index = *self.visible.get(self.selected)?;
+        return Some(self.elements[index].1.clone());
     }
 
     /// Get the extra information of the currently selected item.
     pub fn get_selected_extra(&self) -> Option<T> {
-        if self.elements.len() == 0 || self.selected >= self.elements.len() {
-            return None;
-        }
-        return Some(self.elements[self.selected].0.clone());
+        let index = *self.visible.get(self.selected)?;
+        return Some(self.elements[index].0.clone());
     }
 
     /// Show the list.
     pub fn show(&self, stdout: &mut dyn Write) {
         self.rect.show_border(stdout);
 
-        let offset = cmp::max(0, self.selected as isize - self.rect.get_dimensions().1 as isize + 1);
+        let offset = cmp::min(self.scroll, self.max_scroll());
+        let prefix_width = self.prefix_width();
 
-        for (index, element) in self.elements.iter().enumerate() {
-            if index < offset as usize {
-                continue;
+        let height = self.rect.get_dimensions().1 as usize;
+        let rect = match &self.scrollbar {
+            Some(scrollbar) => scrollbar.content(&self.rect),
+            None => self.rect.clone(),
+        };
+
+        for (position, index) in self.visible.iter().enumerate().skip(offset).take(height) {
+            let element = &self.elements[*index];
+            let selected = position == self.selected;
+            let marked = self.multi_select && self.marked[*index];
+            let style = match (self.style_theme, self.marked_theme) {
+                (Some(theme), _) => theme(element.0.clone(), selected, marked),
+                (None, Some(theme)) if self.multi_select => {
+                    let (fg_color, bg_color) = theme(element.0.clone(), selected, marked);
+                    Style::from_colors(fg_color, bg_color)
+                }
+                _ => {
+                    let (fg_color, bg_color) = (self.theme)(element.0.clone(), selected || marked);
+                    Style::from_colors(fg_color, bg_color)
+                }
+            };
+            let highlight = style.clone().fg(self.highlight);
+
+            let prefix = format!("{:>1$}", self.prefix(*index, marked), prefix_width);
+
+            // Split the line into parts, so the matched characters can be highlighted.
+            let mut parts: Vec<(&str, &Style)> = vec![];
+            if !prefix.is_empty() {
+                parts.push((&prefix, &style));
             }
-            if index >= offset as usize + self.rect.get_dimensions().1 as usize {
-                return;
+            let mut start = 0;
+            let mut highlighted = false;
+            for (i, (byte, _)) in element.1.char_indices().enumerate() {
+                let matched = self.matches[position].contains(&i);
+                if matched != highlighted {
+                    let part_style = if highlighted { &highlight } else { &style };
+                    if byte > start {
+                        parts.push((&element.1[start..byte], part_style));
+                    }
+                    start = byte;
+                    highlighted = matched;
+                }
             }
+            let part_style = if highlighted { &highlight } else { &style };
+            parts.push((&element.1[start..], part_style));
 
-            let (fg_color, bg_color) =
-                (self.theme)(element.0.clone(), index == self.selected);
-            self.rect
-                .write_colored_trimmed_line(stdout, &element.1, index as u16 - offset as u16, &*fg_color, &*bg_color)
+            rect.write_styled_trimmed_parts(stdout, &parts, (position - offset) as u16)
                 .unwrap();
         }
+
+        if let Some(scrollbar) = &self.scrollbar {
+            scrollbar.show(stdout, &self.rect, self.visible.len(), offset);
+        }
     }
 
-    /// Get the currently selected index.
+    /// Get the index of the currently selected item in the list given to [ListRectColored.set_elements].
+    /// This stays the same when a filter is set.
     pub fn get_index(&self) -> usize {
-        return self.selected.clone();
+        return self.visible.get(self.selected).copied().unwrap_or(0);
+    }
+
+    /// Sets how many elements are kept visible above and below the selected one when moving the
+    /// cursor, like `scrolloff` in vim. The default is 0.
+    pub fn set_scrolloff(&mut self, scrolloff: usize) {
+        self.scrolloff = scrolloff;
+        self.scroll_to_selected();
+    }
+
+    /// Sets the scrollbar shown when not all elements fit in the rect. The default is none.
+    pub fn set_scrollbar(&mut self, scrollbar: Option<Scrollbar>) {
+        self.scrollbar = scrollbar;
+    }
+
+    /// Get the index of the first shown element among the elements matching the filter.
+    pub fn get_scroll(&self) -> usize {
+        return self.scroll;
+    }
+
+    /// Scrolls the view by the given number of elements without moving the cursor.
+    /// Negative numbers scroll up. The view stops at the ends of the list.
+    pub fn scroll_by(&mut self, elements: isize) {
+        let scroll = self.scroll as isize + elements;
+        self.scroll = cmp::min(cmp::max(scroll, 0) as usize, self.max_scroll());
+    }
+
+    /// Scrolls the view so that the selected element is in the middle of the rect.
+    pub fn center(&mut self) {
+        let height = self.rect.get_dimensions().1 as usize;
+        self.scroll = cmp::min(self.selected.saturating_sub(height / 2), self.max_scroll());
+    }
+
+    /// The largest scroll at which the rect is still filled.
+    fn max_scroll(&self) -> usize {
+        return self
+            .visible
+            .len()
+            .saturating_sub(self.rect.get_dimensions().1 as usize);
+    }
+
+    /// Selects the element at the given position and scrolls as little as possible to show it.
+    fn select(&mut self, position: usize) {
+        self.selected = position;
+        self.scroll_to_selected();
+    }
+
+    /// Selects the element at the given position. If it is not shown, it will be centered.
+    fn select_centered(&mut self, position: usize) {
+        let height = self.rect.get_dimensions().1 as usize;
+        self.selected = position;
+        if position < self.scroll || position >= self.scroll + height {
+            self.center();
+        }
+        self.scroll_to_selected();
+    }
+
+    /// Scrolls as little as possible to show the selected element with the scrolloff around it.
+    fn scroll_to_selected(&mut self) {
+        let height = self.rect.get_dimensions().1 as usize;
+        if height == 0 {
+            return;
+        }
+        let margin = cmp::min(self.scrolloff, (height - 1) / 2);
+        if self.selected < self.scroll + margin {
+            self.scroll = self.selected.saturating_sub(margin);
+        }
+        if self.selected + margin >= self.scroll + height {
+            self.scroll = self.selected + margin + 1 - height;
+        }
+        self.scroll = cmp::min(self.scroll, self.max_scroll());
+    }
+
+    /// Sets whether [ListRectColored.next] and [ListRectColored.prev] wrap around at the ends of
+    /// the list. The default is `true`.
+    pub fn set_wrap_around(&mut self, wrap_around: bool) {
+        self.wrap_around = wrap_around;
     }
 
-    /// Moves the cursur to the next element. The selection will wrap around.
+    /// Moves the cursur to the next element. The selection will wrap around if enabled.
     pub fn next(&mut self) {
-        self.selected = (self.selected as i32 + 1).rem_euclid(self.elements.len() as i32) as usize;
+        if self.visible.is_empty() {
+            return;
+        }
+        if self.wrap_around {
+            self.select((self.selected + 1) % self.visible.len());
+        } else {
+            self.select(cmp::min(self.selected + 1, self.visible.len() - 1));
+        }
     }
 
-    /// Move the cursor to the previous element. The selection will wrap around.
+    /// Move the cursor to the previous element. The selection will wrap around if enabled.
     pub fn prev(&mut self) {
-        self.selected = (self.selected as i32 - 1).rem_euclid(self.elements.len() as i32) as usize;
+        if self.visible.is_empty() {
+            return;
+        }
+        if self.wrap_around {
+            self.select((self.selected + self.visible.len() - 1) % self.visible.len());
+        } else {
+            self.select(self.selected.saturating_sub(1));
+        }
+    }
+
+    /// Moves the cursor down by the height of the rect. Stops at the last element.
+    pub fn page_down(&mut self) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let height = cmp::max(self.rect.get_dimensions().1 as usize, 1);
+        self.select(cmp::min(self.selected + height, self.visible.len() - 1));
+    }
+
+    /// Moves the cursor up by the height of the rect. Stops at the first element.
+    pub fn page_up(&mut self) {
+        let height = cmp::max(self.rect.get_dimensions().1 as usize, 1);
+        self.select(self.selected.saturating_sub(height));
     }
+
+    /// Moves the cursor to the first element.
+    pub fn first(&mut self) {
+        self.select(0);
+    }
+
+    /// Moves the cursor to the last element.
+    pub fn last(&mut self) {
+        self.select(self.visible.len().saturating_sub(1));
+    }
+
+    /// Moves the cursor to the element with the given index in the list given to
+    /// [ListRectColored.set_elements]. Returns whether the element is shown and could be selected.
+    pub fn jump(&mut self, index: usize) -> bool {
+        match self.visible.iter().position(|visible| *visible == index) {
+            Some(position) => {
+                self.select_centered(position);
+                return true;
+            }
+            None => return false,
+        }
+    }
+
+    /// Moves the cursor to the next element starting with the given letter, ignoring the case.
+    /// The search wraps around. Returns whether such an element was found.
+    pub fn jump_to_letter(&mut self, letter: char) -> bool {
+        let letter = fold(letter);
+        let count = self.visible.len();
+        for step in 1..=count {
+            let position = (self.selected + step) % count;
+            let first = self.elements[self.visible[position]].1.chars().next().map(fold);
+            if first == Some(letter) {
+                self.select_centered(position);
+                return true;
+            }
+        }
+        return false;
+    }
+
+    /// Moves the cursor according to the key. Returns whether the key was used.
+    /// Typing a letter jumps to the next element starting with it. In the multi selection mode,
+    /// space marks or unmarks the selected item.
+    pub fn handle_key(&mut self, key: Key) -> bool {
+        match key {
+            Key::Down => self.next(),
+            Key::Up => self.prev(),
+            Key::PageDown => self.page_down(),
+            Key::PageUp => self.page_up(),
+            Key::Home => self.first(),
+            Key::End => self.last(),
+            Key::Char(' ') if self.multi_select => self.toggle_mark(),
+            Key::Char(c) if c.is_alphanumeric() => return self.jump_to_letter(c),
+            _ => return false,
+        }
+        return true;
+    }
+}
+
+/// Converts a number starting at 1 to letters: a, b, ..., z, aa, ab, ...
+fn letters(mut number: usize) -> String {
+    let mut letters = vec![];
+    while number > 0 {
+        number -= 1;
+        letters.push((b'a' + (number % 26) as u8) as char);
+        number /= 26;
+    }
+    return letters.iter().rev().collect();
+}
+
+/// Converts a number starting at 1 to roman numerals.
+fn roman(mut number: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS.iter() {
+        while number >= *value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+    return roman;
+}
+
+/// Lowercases a character for matching without case.
+fn fold(c: char) -> char {
+    return c.to_lowercase().next().unwrap_or(c);
+}
+
+/// Finds the first occurrence of the filter in the chars.
+/// Returns the score and the positions of the matched chars.
+fn substring_match(chars: &[char], filter: &[char]) -> Option<(i32, Vec<usize>)> {
+    if filter.is_empty() {
+        return Some((0, vec![]));
+    }
+    let start = chars.windows(filter.len()).position(|window| window == filter)?;
+    return Some((0, (start..start + filter.len()).collect()));
+}
+
+/// Finds the chars of the filter in order in the chars.
+/// Consecutive matches and matches at the start of words score higher, gaps score lower.
+/// Returns the score and the positions of the matched chars.
+fn fuzzy_match(chars: &[char], filter: &[char]) -> Option<(i32, Vec<usize>)> {
+    let mut positions = vec![];
+    let mut score = 0;
+    let mut next = 0;
+
+    for c in filter.iter() {
+        let position = next + chars[next..].iter().position(|x| x == c)?;
+        score += 1;
+        if position > 0 && positions.last() == Some(&(position - 1)) {
+            score += 5;
+        } else if !positions.is_empty() {
+            score -= cmp::min(position - next, 5) as i32;
+        }
+        if position == 0 || !chars[position - 1].is_alphanumeric() {
+            score += 3;
+        }
+        positions.push(position);
+        next = position + 1;
+    }
+
+    return Some((score, positions));
 }
 
 impl crate::rect::Rect {
     /// Creates a list from the rect.
-    pub fn into_list_colored<'a, T>(
-        self,
-        theme: &'a dyn Fn(
-            T,
-            bool,
-        ) -> (
-            &'static dyn termion::color::Color,
-            &'static dyn termion::color::Color,
-        ),
-    ) -> Box<ListRectColored<'a, T>> {
+    pub fn into_list_colored<'a, T>(self, theme: Theme<'a, T>) -> Box<ListRectColored<'a, T>> {
         return Box::new(ListRectColored::<T> {
             rect: self,
             elements: vec![],
             selected: 0,
-            theme: theme,
+            theme,
+            multi_select: false,
+            marked: vec![],
+            mark_anchor: None,
+            marked_theme: None,
+            style_theme: None,
+            filter: String::new(),
+            filter_mode: FilterMode::SUBSTRING,
+            visible: vec![],
+            matches: vec![],
+            highlight: &termion::color::Yellow,
+            enumeration: Enumeration::NONE,
+            wrap_around: true,
+            scroll: 0,
+            scrolloff: 0,
+            scrollbar: None,
         });
     }
 }
@@ -1 +1,6 @@
+mod cursor;
+pub mod inputrect;
 pub mod listrectcolored;
+pub mod tablerect;
+pub mod textarearect;
+pub mod treerect;
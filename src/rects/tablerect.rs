@@ -0,0 +1,314 @@
+use crate::layout::Constraint;
+use crate::rect::Rect;
+use crate::rects::cursor::Cursor;
+use crate::rects::listrectcolored::Theme;
+use crate::text;
+use crate::text::Alignment;
+
+use std::cmp;
+use std::io::Write;
+use termion::event::Key;
+
+/// Uses a rect to make a table. The first row shows the headers of the columns and stays in
+/// place while the rows below are scrolled. One row can be selected.
+/// The rows can be sorted by any column, see [TableRect.sort_by].
+pub struct TableRect<'a, T> {
+    rect: Rect,
+    columns: Vec<Column>,
+    rows: Vec<(T, Vec<String>)>,
+    order: Vec<usize>,
+    cursor: Cursor,
+    sort: Option<(usize, bool)>,
+    theme: Theme<'a, T>,
+    header_fg: &'static dyn termion::color::Color,
+    header_bg: &'static dyn termion::color::Color,
+}
+
+/// A column of a [TableRect].
+/// The widths of the columns are computed from their [Constraint]s the same way as the sizes of a
+/// split [Layout], the columns are separated by one space.
+#[derive(Clone)]
+pub struct Column {
+    pub header: String,
+    pub constraint: Constraint,
+    pub alignment: Alignment,
+}
+
+impl Column {
+    /// Creates a new column.
+    pub fn new(header: &str, constraint: Constraint, alignment: Alignment) -> Column {
+        return Column {
+            header: String::from(header),
+            constraint,
+            alignment,
+        };
+    }
+}
+
+impl<'a, T: Clone> TableRect<'a, T> {
+    /// Clears the rect
+    pub fn clear(&self, stdout: &mut dyn Write) {
+        self.rect.clear(stdout);
+    }
+
+    /// Replaces the rect the table is shown in, e.g. after the terminal was resized.
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.scroll_to_selected();
+    }
+
+    /// Set the columns. Rows with less cells than columns are filled with empty cells.
+    pub fn set_columns(&mut self, columns: Vec<Column>) {
+        self.columns = columns;
+        self.sort = None;
+        self.resort();
+    }
+
+    /// Set the rows. Each row consists of extra information and the content of its cells.
+    pub fn set_rows(&mut self, rows: Vec<(T, Vec<String>)>) {
+        self.rows = rows;
+        self.order = vec![];
+        self.cursor.selected = 0;
+        self.cursor.scroll = 0;
+        self.resort();
+    }
+
+    /// Change one single row in the table. The rows are sorted again.
+    pub fn set_row(&mut self, index: usize, row: (T, Vec<String>)) {
+        if index >= self.rows.len() {
+            return;
+        }
+        self.rows[index] = row;
+        self.resort();
+    }
+
+    /// Sets the theme of the rows.
+    /// The theme is a function that takes the extra information given with each row and whether
+    /// it is selected and returns the foreground color and background color.
+    pub fn set_theme(&mut self, theme: Theme<'a, T>) {
+        self.theme = theme;
+    }
+
+    /// Sets the foreground color and background color of the header row.
+    pub fn set_header_colors(
+        &mut self,
+        fg_color: &'static dyn termion::color::Color,
+        bg_color: &'static dyn termion::color::Color,
+    ) {
+        self.header_fg = fg_color;
+        self.header_bg = bg_color;
+    }
+
+    /// Sorts the rows by the column with the given index. Numbers are compared as numbers and
+    /// come before all other cells, which are compared as text. Rows with equal cells keep their
+    /// order.
+    /// The selected row stays selected.
+    pub fn sort_by(&mut self, column: usize, descending: bool) {
+        if column >= self.columns.len() {
+            return;
+        }
+        self.sort = Some((column, descending));
+        self.resort();
+    }
+
+    /// Sorts by the given column. If the rows are already sorted by it, the order is reversed.
+    pub fn toggle_sort(&mut self, column: usize) {
+        let descending = self.sort == Some((column, false));
+        self.sort_by(column, descending);
+    }
+
+    /// Shows the rows in the order they were given.
+    pub fn unsort(&mut self) {
+        self.sort = None;
+        self.resort();
+    }
+
+    /// Get the index of the column the rows are sorted by and whether they are sorted descending.
+    pub fn get_sort(&self) -> Option<(usize, bool)> {
+        return self.sort;
+    }
+
+    /// Computes the order of the rows.
+    fn resort(&mut self) {
+        let selected = self.order.get(self.cursor.selected).copied();
+
+        let mut order: Vec<usize> = (0..self.rows.len()).collect();
+        if let Some((column, descending)) = self.sort {
+            let cell = |index: usize| -> &str {
+                return self.rows[index].1.get(column).map(|c| c.as_str()).unwrap_or("");
+            };
+            order.sort_by(|a, b| {
+                let ordering = match (cell(*a).trim().parse::<f64>(), cell(*b).trim().parse::<f64>()) {
+                    (Ok(a), Ok(b)) => a.total_cmp(&b),
+                    (Ok(_), Err(_)) => cmp::Ordering::Less,
+                    (Err(_), Ok(_)) => cmp::Ordering::Greater,
+                    (Err(_), Err(_)) => cell(*a).cmp(cell(*b)),
+                };
+                return if descending { ordering.reverse() } else { ordering };
+            });
+        }
+        self.order = order;
+
+        self.cursor.selected = selected
+            .and_then(|selected| self.order.iter().position(|index| *index == selected))
+            .unwrap_or(0);
+        self.scroll_to_selected();
+    }
+
+    /// Get the cells of the currently selected row.
+    pub fn get_selected(&self) -> Option<Vec<String>> {
+        let index = *self.order.get(self.cursor.selected)?;
+        return Some(self.rows[index].1.clone());
+    }
+
+    /// Get the extra information of the currently selected row.
+    pub fn get_selected_extra(&self) -> Option<T> {
+        let index = *self.order.get(self.cursor.selected)?;
+        return Some(self.rows[index].0.clone());
+    }
+
+    /// Get the index of the currently selected row in the rows given to [TableRect.set_rows].
+    /// This stays the same when the rows are sorted.
+    pub fn get_index(&self) -> usize {
+        return self.order.get(self.cursor.selected).copied().unwrap_or(0);
+    }
+
+    /// The amount of rows that can be shown below the header.
+    fn height(&self) -> usize {
+        return (self.rect.get_dimensions().1 as usize).saturating_sub(1);
+    }
+
+    /// Scrolls as little as possible to show the selected row.
+    fn scroll_to_selected(&mut self) {
+        self.cursor.scroll_to_selected(self.rows.len(), self.height());
+    }
+
+    /// Moves the cursor to the next row. Stops at the last row.
+    pub fn next(&mut self) {
+        self.cursor.next(self.rows.len(), self.height());
+    }
+
+    /// Moves the cursor to the previous row. Stops at the first row.
+    pub fn prev(&mut self) {
+        self.cursor.prev(self.rows.len(), self.height());
+    }
+
+    /// Moves the cursor down by the amount of rows shown. Stops at the last row.
+    pub fn page_down(&mut self) {
+        self.cursor.page_down(self.rows.len(), self.height());
+    }
+
+    /// Moves the cursor up by the amount of rows shown. Stops at the first row.
+    pub fn page_up(&mut self) {
+        self.cursor.page_up(self.rows.len(), self.height());
+    }
+
+    /// Moves the cursor to the first row.
+    pub fn first(&mut self) {
+        self.cursor.first(self.rows.len(), self.height());
+    }
+
+    /// Moves the cursor to the last row.
+    pub fn last(&mut self) {
+        self.cursor.last(self.rows.len(), self.height());
+    }
+
+    /// Moves the cursor according to the key. Returns whether the key was used.
+    pub fn handle_key(&mut self, key: Key) -> bool {
+        match key {
+            Key::Down => self.next(),
+            Key::Up => self.prev(),
+            Key::PageDown => self.page_down(),
+            Key::PageUp => self.page_up(),
+            Key::Home => self.first(),
+            Key::End => self.last(),
+            _ => return false,
+        }
+        return true;
+    }
+
+    /// Get the widths of the columns.
+    fn widths(&self) -> Vec<usize> {
+        let width = self.rect.get_dimensions().0;
+        let separators = self.columns.len().saturating_sub(1) as u16;
+        let constraints: Vec<Constraint> = self.columns.iter().map(|c| c.constraint.clone()).collect();
+        return Constraint::sizes(&constraints, width.saturating_sub(separators))
+            .into_iter()
+            .map(|w| w as usize)
+            .collect();
+    }
+
+    /// Get the line of the cells, aligned in their columns.
+    fn line(&self, cells: &[String], widths: &[usize]) -> String {
+        let cells: Vec<String> = self
+            .columns
+            .iter()
+            .zip(widths.iter())
+            .enumerate()
+            .map(|(i, (column, width))| {
+                let cell = cells.get(i).map(|c| c.as_str()).unwrap_or("");
+                return text::align(cell, *width, column.alignment);
+            })
+            .collect();
+        return cells.join(" ");
+    }
+
+    /// Show the table. The column the rows are sorted by is marked with an arrow in the header.
+    pub fn show(&self, stdout: &mut dyn Write) {
+        self.rect.show_border(stdout);
+        if self.rect.get_dimensions().1 == 0 {
+            return;
+        }
+
+        let widths = self.widths();
+        let headers: Vec<String> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| match self.sort {
+                Some((sorted, descending)) if sorted == i => {
+                    format!("{} {}", column.header, if descending { "▼" } else { "▲" })
+                }
+                _ => column.header.clone(),
+            })
+            .collect();
+        self.rect
+            .write_colored_trimmed_line(stdout, &self.line(&headers, &widths), 0, self.header_fg, self.header_bg)
+            .unwrap();
+
+        for y in 0..self.height() {
+            let position = self.cursor.scroll + y;
+            match self.order.get(position) {
+                Some(index) => {
+                    let row = &self.rows[*index];
+                    let (fg_color, bg_color) = (self.theme)(row.0.clone(), position == self.cursor.selected);
+                    self.rect
+                        .write_colored_trimmed_line(stdout, &self.line(&row.1, &widths), y as u16 + 1, fg_color, bg_color)
+                        .unwrap();
+                }
+                None => {
+                    let width = self.rect.get_dimensions().0 as usize;
+                    self.rect.write(stdout, &" ".repeat(width), 0, y as u16 + 1).unwrap();
+                }
+            }
+        }
+    }
+}
+
+impl crate::rect::Rect {
+    /// Creates a table from the rect.
+    /// The theme is used for the rows, see [TableRect.set_theme].
+    pub fn into_table<'a, T>(self, theme: Theme<'a, T>) -> Box<TableRect<'a, T>> {
+        return Box::new(TableRect::<T> {
+            rect: self,
+            columns: vec![],
+            rows: vec![],
+            order: vec![],
+            cursor: Cursor::new(),
+            sort: None,
+            theme,
+            header_fg: &termion::color::Reset,
+            header_bg: &termion::color::Reset,
+        });
+    }
+}
@@ -0,0 +1,629 @@
+use crate::rect::Rect;
+use crate::scrollbar::Scrollbar;
+use crate::text;
+
+use std::cmp;
+use std::io::Write;
+use termion::event::Key;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Uses a rect to make a multi line text area that can be edited.
+/// Lines longer than the rect are wrapped between words, the text is scrolled vertically to keep
+/// the cursor visible.
+/// Positions in the text are given as (line, character), both (0,0)-based.
+pub struct TextAreaRect {
+    rect: Rect,
+    lines: Vec<String>,
+    cursor: (usize, usize),
+    anchor: Option<(usize, usize)>,
+    column: Option<usize>,
+    scroll: usize,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    typing: bool,
+    scrollbar: Option<Scrollbar>,
+}
+
+/// The state of the text that can be restored by undo and redo.
+#[derive(Clone)]
+struct Snapshot {
+    lines: Vec<String>,
+    cursor: (usize, usize),
+}
+
+impl TextAreaRect {
+    /// Clears the rect
+    pub fn clear(&self, stdout: &mut dyn Write) {
+        self.rect.clear(stdout);
+    }
+
+    /// Replaces the rect the text area is shown in, e.g. after the terminal was resized.
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.scroll_to_cursor();
+    }
+
+    /// Sets the scrollbar shown when not all rows fit in the rect. The default is none.
+    pub fn set_scrollbar(&mut self, scrollbar: Option<Scrollbar>) {
+        self.scrollbar = scrollbar;
+        self.scroll_to_cursor();
+    }
+
+    /// The rect the text is shown in, next to the scrollbar.
+    fn content(&self) -> Rect {
+        return match &self.scrollbar {
+            Some(scrollbar) => scrollbar.content(&self.rect),
+            None => self.rect.clone(),
+        };
+    }
+
+    /// Get the whole text. Lines are separated by "\n".
+    pub fn get_text(&self) -> String {
+        return self.lines.join("\n");
+    }
+
+    /// Replaces the whole text. The cursor is moved to the start and the history is cleared.
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = text.split('\n').map(String::from).collect();
+        self.cursor = (0, 0);
+        self.anchor = None;
+        self.column = None;
+        self.scroll = 0;
+        self.undo.clear();
+        self.redo.clear();
+        self.typing = false;
+    }
+
+    /// Get the position of the cursor.
+    pub fn get_cursor(&self) -> (usize, usize) {
+        return self.cursor;
+    }
+
+    /// Get the selected text if there is a selection.
+    pub fn get_selection(&self) -> Option<String> {
+        let (start, end) = self.selection()?;
+        let mut selected = vec![];
+        for line in start.0..=end.0 {
+            let from = if line == start.0 { start.1 } else { 0 };
+            let to = if line == end.0 { end.1 } else { self.len(line) };
+            let text = &self.lines[line];
+            selected.push(&text[text::byte_index(text, from)..text::byte_index(text, to)]);
+        }
+        return Some(selected.join("\n"));
+    }
+
+    /// Selects the whole text.
+    pub fn select_all(&mut self) {
+        self.anchor = Some((0, 0));
+        let last = self.lines.len() - 1;
+        self.cursor = (last, self.len(last));
+        self.column = None;
+        self.scroll_to_cursor();
+    }
+
+    /// Removes the selection without changing the text.
+    pub fn clear_selection(&mut self) {
+        self.anchor = None;
+    }
+
+    /// Inserts a character at the cursor, replacing the selection.
+    /// A "\n" starts a new line, other control characters are ignored.
+    pub fn insert(&mut self, c: char) {
+        if c.is_control() && c != '\n' {
+            return;
+        }
+        self.save(!c.is_whitespace());
+        self.delete_selected();
+        self.insert_at_cursor(&c.to_string());
+    }
+
+    /// Inserts a string at the cursor, replacing the selection.
+    pub fn insert_str(&mut self, str: &str) {
+        self.save(false);
+        self.delete_selected();
+        self.insert_at_cursor(str);
+    }
+
+    /// Deletes the selection or the character before the cursor.
+    pub fn backspace(&mut self) {
+        if self.selection().is_none() {
+            if self.cursor == (0, 0) {
+                return;
+            }
+            self.save(false);
+            self.anchor = Some(self.cursor);
+            self.move_left();
+        } else {
+            self.save(false);
+        }
+        self.delete_selected();
+    }
+
+    /// Deletes the selection or the character at the cursor.
+    pub fn delete(&mut self) {
+        if self.selection().is_none() {
+            let last = self.lines.len() - 1;
+            if self.cursor == (last, self.len(last)) {
+                return;
+            }
+            self.save(false);
+            self.anchor = Some(self.cursor);
+            self.move_right();
+        } else {
+            self.save(false);
+        }
+        self.delete_selected();
+    }
+
+    /// Reverts the last change. Returns whether there was something to revert.
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(snapshot) => {
+                let current = self.snapshot();
+                self.redo.push(current);
+                self.restore(snapshot);
+                return true;
+            }
+            None => return false,
+        }
+    }
+
+    /// Applies the last reverted change again. Returns whether there was something to apply.
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(snapshot) => {
+                let current = self.snapshot();
+                self.undo.push(current);
+                self.restore(snapshot);
+                return true;
+            }
+            None => return false,
+        }
+    }
+
+    /// Moves the cursor one character to the left. Moving over the start of a line goes to
+    /// the end of the previous line. If selecting, the selection is extended.
+    pub fn left(&mut self, select: bool) {
+        self.start_move(select);
+        self.move_left();
+        self.end_move(true);
+    }
+
+    /// Moves the cursor one character to the right. Moving over the end of a line goes to
+    /// the start of the next line. If selecting, the selection is extended.
+    pub fn right(&mut self, select: bool) {
+        self.start_move(select);
+        self.move_right();
+        self.end_move(true);
+    }
+
+    /// Moves the cursor to the start of the previous word.
+    pub fn word_left(&mut self, select: bool) {
+        self.start_move(select);
+        self.move_left();
+        while self.cursor.1 > 0 && self.is_space(self.cursor.0, self.cursor.1) {
+            self.move_left();
+        }
+        while self.cursor.1 > 0 && !self.is_space(self.cursor.0, self.cursor.1 - 1) {
+            self.move_left();
+        }
+        self.end_move(true);
+    }
+
+    /// Moves the cursor to the start of the next word.
+    pub fn word_right(&mut self, select: bool) {
+        self.start_move(select);
+        if self.cursor.1 == self.len(self.cursor.0) {
+            self.move_right();
+        }
+        let line = self.cursor.0;
+        while self.cursor.1 < self.len(line) && !self.is_space(line, self.cursor.1) {
+            self.move_right();
+        }
+        while self.cursor.1 < self.len(line) && self.is_space(line, self.cursor.1) {
+            self.move_right();
+        }
+        self.end_move(true);
+    }
+
+    /// Moves the cursor one row up, rows of wrapped lines are counted separately.
+    pub fn up(&mut self, select: bool) {
+        self.move_rows(-1, select);
+    }
+
+    /// Moves the cursor one row down, rows of wrapped lines are counted separately.
+    pub fn down(&mut self, select: bool) {
+        self.move_rows(1, select);
+    }
+
+    /// Moves the cursor up by the height of the rect.
+    pub fn page_up(&mut self, select: bool) {
+        self.move_rows(-(self.rect.get_dimensions().1 as isize), select);
+    }
+
+    /// Moves the cursor down by the height of the rect.
+    pub fn page_down(&mut self, select: bool) {
+        self.move_rows(self.rect.get_dimensions().1 as isize, select);
+    }
+
+    /// Moves the cursor to the start of the line.
+    pub fn home(&mut self, select: bool) {
+        self.start_move(select);
+        self.cursor.1 = 0;
+        self.end_move(true);
+    }
+
+    /// Moves the cursor to the end of the line.
+    pub fn end(&mut self, select: bool) {
+        self.start_move(select);
+        self.cursor.1 = self.len(self.cursor.0);
+        self.end_move(true);
+    }
+
+    /// Moves the cursor to the start of the text.
+    pub fn top(&mut self, select: bool) {
+        self.start_move(select);
+        self.cursor = (0, 0);
+        self.end_move(true);
+    }
+
+    /// Moves the cursor to the end of the text.
+    pub fn bottom(&mut self, select: bool) {
+        self.start_move(select);
+        let last = self.lines.len() - 1;
+        self.cursor = (last, self.len(last));
+        self.end_move(true);
+    }
+
+    /// Edits the text or moves the cursor according to the key. Returns whether the key was used.
+    /// Holding shift while moving selects, Ctrl+Z undoes, Ctrl+Y redoes and Ctrl+A selects all.
+    pub fn handle_key(&mut self, key: Key) -> bool {
+        match key {
+            Key::Char('\t') => self.insert_str("    "),
+            Key::Char(c) if c == '\n' || !c.is_control() => self.insert(c),
+            Key::Backspace => self.backspace(),
+            Key::Delete => self.delete(),
+            Key::Left => self.left(false),
+            Key::ShiftLeft => self.left(true),
+            Key::Right => self.right(false),
+            Key::ShiftRight => self.right(true),
+            Key::CtrlLeft | Key::AltLeft => self.word_left(false),
+            Key::CtrlRight | Key::AltRight => self.word_right(false),
+            Key::Up => self.up(false),
+            Key::ShiftUp => self.up(true),
+            Key::Down => self.down(false),
+            Key::ShiftDown => self.down(true),
+            Key::PageUp => self.page_up(false),
+            Key::PageDown => self.page_down(false),
+            Key::Home => self.home(false),
+            Key::End => self.end(false),
+            Key::CtrlHome => self.top(false),
+            Key::CtrlEnd => self.bottom(false),
+            Key::Ctrl('z') => {
+                self.undo();
+            }
+            Key::Ctrl('y') => {
+                self.redo();
+            }
+            Key::Ctrl('a') => self.select_all(),
+            _ => return false,
+        }
+        return true;
+    }
+
+    /// Show the text area and move the cursor of the terminal to the cursor of the text.
+    /// The selection is shown inverted.
+    pub fn show(&self, stdout: &mut dyn Write) {
+        let rect = self.content();
+        let (width, height) = rect.get_dimensions();
+        let rows = self.rows();
+        let selection = self.selection();
+
+        for y in 0..height {
+            let mut x = 0;
+            if let Some(&(line, start, end)) = rows.get(self.scroll + y as usize) {
+                let graphemes: Vec<&str> = self.lines[line].graphemes(true).collect();
+
+                // The selected characters of this row.
+                let (from, to) = match selection {
+                    Some((first, last)) if first.0 <= line && line <= last.0 => {
+                        let from = if first.0 == line { first.1 } else { 0 };
+                        let to = if last.0 == line { last.1 } else { graphemes.len() };
+                        (cmp::max(from, start), cmp::min(to, end))
+                    }
+                    _ => (end, end),
+                };
+                let from = cmp::min(from, end);
+                let to = cmp::max(from, to);
+
+                let parts = [
+                    (start, from, false),
+                    (from, to, true),
+                    (to, end, false),
+                ];
+                for (part_start, part_end, selected) in parts {
+                    if part_start >= part_end {
+                        continue;
+                    }
+                    let part = graphemes[part_start..part_end].concat();
+                    if selected {
+                        rect.write_colored(
+                            stdout,
+                            &part,
+                            x,
+                            y,
+                            &termion::color::Black,
+                            &termion::color::White,
+                        )
+                        .unwrap();
+                    } else {
+                        rect.write(stdout, &part, x, y).unwrap();
+                    }
+                    x += text::width(&part) as u16;
+                }
+            }
+            if x < width {
+                rect.write(stdout, &" ".repeat((width - x) as usize), x, y)
+                    .unwrap();
+            }
+        }
+
+        if let Some(scrollbar) = &self.scrollbar {
+            scrollbar.show(stdout, &self.rect, rows.len(), self.scroll);
+        }
+
+        let (row, column) = self.cursor_row();
+        if row >= self.scroll && row < self.scroll + height as usize {
+            rect.set_cursor(stdout, column as u16, (row - self.scroll) as u16);
+        }
+    }
+
+    /// The amount of characters in the line.
+    fn len(&self, line: usize) -> usize {
+        return text::grapheme_count(&self.lines[line]);
+    }
+
+    /// Whether the character at the position is whitespace.
+    fn is_space(&self, line: usize, index: usize) -> bool {
+        return self.lines[line]
+            .graphemes(true)
+            .nth(index)
+            .map(|g| g.chars().all(char::is_whitespace))
+            .unwrap_or(false);
+    }
+
+    /// Get the start and end of the selection, ordered.
+    fn selection(&self) -> Option<((usize, usize), (usize, usize))> {
+        let anchor = self.anchor?;
+        if anchor == self.cursor {
+            return None;
+        }
+        return Some((cmp::min(anchor, self.cursor), cmp::max(anchor, self.cursor)));
+    }
+
+    /// Splits a line into the rows it is shown in. The rows are given as ranges of characters.
+    /// Lines are broken after spaces if possible. A full last row is followed by an empty row for
+    /// the cursor.
+    fn wrap(line: &str, width: usize) -> Vec<(usize, usize)> {
+        let widths: Vec<usize> = line.graphemes(true).map(text::width).collect();
+        let spaces: Vec<bool> = line.graphemes(true).map(|g| g == " ").collect();
+
+        let mut rows = vec![];
+        let mut start = 0;
+        let mut used = 0;
+        let mut last_break = 0;
+        for i in 0..widths.len() {
+            if used + widths[i] > width && i > start {
+                let end = if last_break > start { last_break } else { i };
+                rows.push((start, end));
+                start = end;
+                used = widths[start..i].iter().sum();
+            }
+            used += widths[i];
+            if spaces[i] {
+                last_break = i + 1;
+            }
+        }
+
+        if used >= width && start < widths.len() {
+            rows.push((start, widths.len()));
+            start = widths.len();
+        }
+        rows.push((start, widths.len()));
+        return rows;
+    }
+
+    /// All rows of the text as (line, first character, end character).
+    fn rows(&self) -> Vec<(usize, usize, usize)> {
+        let width = self.content().get_dimensions().0 as usize;
+        let mut rows = vec![];
+        for (line, text) in self.lines.iter().enumerate() {
+            rows.extend(
+                TextAreaRect::wrap(text, width)
+                    .into_iter()
+                    .map(|(start, end)| (line, start, end)),
+            );
+        }
+        return rows;
+    }
+
+    /// Get the row the cursor is in and its column in that row.
+    fn cursor_row(&self) -> (usize, usize) {
+        let rows = self.rows();
+        let (line, index) = self.cursor;
+        for (row, &(l, start, end)) in rows.iter().enumerate() {
+            let last = rows.get(row + 1).map(|r| r.0 != l).unwrap_or(true);
+            if l == line && start <= index && (index < end || (index == end && last)) {
+                let column = self.lines[line]
+                    .graphemes(true)
+                    .skip(start)
+                    .take(index - start)
+                    .map(text::width)
+                    .sum();
+                return (row, column);
+            }
+        }
+        return (0, 0);
+    }
+
+    /// Moves the cursor by the amount of rows, staying in the same column if possible.
+    fn move_rows(&mut self, amount: isize, select: bool) {
+        self.start_move(select);
+        let rows = self.rows();
+        let (row, column) = self.cursor_row();
+        let column = self.column.unwrap_or(column);
+        let target = cmp::min(
+            cmp::max(row as isize + amount, 0) as usize,
+            rows.len() - 1,
+        );
+
+        let (line, start, end) = rows[target];
+        let last = rows.get(target + 1).map(|r| r.0 != line).unwrap_or(true);
+        let max = if last || end == start { end } else { end - 1 };
+
+        let mut index = start;
+        let mut used = 0;
+        for g in self.lines[line].graphemes(true).skip(start) {
+            used += text::width(g);
+            if used > column || index >= max {
+                break;
+            }
+            index += 1;
+        }
+        self.cursor = (line, index);
+
+        self.end_move(false);
+        self.column = Some(column);
+    }
+
+    /// Moves the cursor one character left without changing the selection.
+    fn move_left(&mut self) {
+        if self.cursor.1 > 0 {
+            self.cursor.1 -= 1;
+        } else if self.cursor.0 > 0 {
+            self.cursor.0 -= 1;
+            self.cursor.1 = self.len(self.cursor.0);
+        }
+    }
+
+    /// Moves the cursor one character right without changing the selection.
+    fn move_right(&mut self) {
+        if self.cursor.1 < self.len(self.cursor.0) {
+            self.cursor.1 += 1;
+        } else if self.cursor.0 + 1 < self.lines.len() {
+            self.cursor = (self.cursor.0 + 1, 0);
+        }
+    }
+
+    /// Starts or ends the selection before moving the cursor.
+    fn start_move(&mut self, select: bool) {
+        if !select {
+            self.anchor = None;
+        } else if self.anchor.is_none() {
+            self.anchor = Some(self.cursor);
+        }
+        self.typing = false;
+    }
+
+    /// Updates the scroll after moving the cursor.
+    fn end_move(&mut self, reset_column: bool) {
+        if reset_column {
+            self.column = None;
+        }
+        self.scroll_to_cursor();
+    }
+
+    /// Scrolls such that the cursor is visible.
+    fn scroll_to_cursor(&mut self) {
+        let height = self.rect.get_dimensions().1 as usize;
+        let rows = self.rows().len();
+        let (row, _) = self.cursor_row();
+
+        self.scroll = cmp::min(self.scroll, rows.saturating_sub(height));
+        if row < self.scroll {
+            self.scroll = row;
+        } else if row >= self.scroll + height {
+            self.scroll = row + 1 - height;
+        }
+    }
+
+    /// Inserts the string at the cursor and moves the cursor behind it.
+    fn insert_at_cursor(&mut self, str: &str) {
+        let (line, index) = self.cursor;
+        let at = text::byte_index(&self.lines[line], index);
+        let rest = self.lines[line].split_off(at);
+
+        let mut inserted = str.split('\n');
+        self.lines[line].push_str(inserted.next().unwrap_or(""));
+        let mut current = line;
+        for next in inserted {
+            current += 1;
+            self.lines.insert(current, String::from(next));
+        }
+
+        self.cursor = (current, self.len(current));
+        self.lines[current].push_str(&rest);
+        self.end_move(true);
+    }
+
+    /// Deletes the selected text and removes the selection.
+    fn delete_selected(&mut self) {
+        if let Some((start, end)) = self.selection() {
+            let at = text::byte_index(&self.lines[end.0], end.1);
+            let rest = self.lines[end.0].split_off(at);
+            let at = text::byte_index(&self.lines[start.0], start.1);
+            self.lines[start.0].truncate(at);
+            self.lines[start.0].push_str(&rest);
+            self.lines.drain(start.0 + 1..=end.0);
+            self.cursor = start;
+        }
+        self.anchor = None;
+        self.end_move(true);
+    }
+
+    /// Get the current state for the history.
+    fn snapshot(&self) -> Snapshot {
+        return Snapshot {
+            lines: self.lines.clone(),
+            cursor: self.cursor,
+        };
+    }
+
+    /// Saves the current state before a change. Typing several characters in a row is saved only
+    /// once, so they are undone together.
+    fn save(&mut self, typing: bool) {
+        if !(typing && self.typing) {
+            let snapshot = self.snapshot();
+            self.undo.push(snapshot);
+        }
+        self.typing = typing;
+        self.redo.clear();
+    }
+
+    /// Restores a state from the history.
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.lines = snapshot.lines;
+        self.cursor = snapshot.cursor;
+        self.anchor = None;
+        self.typing = false;
+        self.end_move(true);
+    }
+}
+
+impl crate::rect::Rect {
+    /// Creates a text area from the rect.
+    pub fn into_text_area(self) -> Box<TextAreaRect> {
+        return Box::new(TextAreaRect {
+            rect: self,
+            lines: vec![String::new()],
+            cursor: (0, 0),
+            anchor: None,
+            column: None,
+            scroll: 0,
+            undo: vec![],
+            redo: vec![],
+            typing: false,
+            scrollbar: None,
+        });
+    }
+}
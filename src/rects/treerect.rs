@@ -0,0 +1,391 @@
+use crate::rect::Rect;
+use crate::rects::cursor::Cursor;
+use crate::rects::listrectcolored::Theme;
+
+use std::io::Write;
+use termion::event::Key;
+
+/// Uses a rect to make a tree of items. Items with children can be expanded and collapsed,
+/// the shown items can be selected like in a list.
+/// Children can be loaded when their parent is expanded for the first time, see [TreeRect.set_loader].
+pub struct TreeRect<'a, T> {
+    rect: Rect,
+    nodes: Vec<Node<T>>,
+    roots: Vec<usize>,
+    visible: Vec<usize>,
+    cursor: Cursor,
+    theme: Theme<'a, T>,
+    loader: Option<Loader<'a, T>>,
+    guides: bool,
+}
+
+/// Loads the children of an item, see [TreeRect.set_loader].
+pub type Loader<'a, T> = &'a dyn Fn(T) -> Vec<TreeItem<T>>;
+
+/// An item given to a [TreeRect] with its extra information, its label and its children.
+/// The children are `None` if they are not known yet and will be loaded on expanding.
+pub struct TreeItem<T> {
+    pub extra: T,
+    pub label: String,
+    pub children: Option<Vec<TreeItem<T>>>,
+}
+
+/// An item in the [TreeRect] with the indices of its parent and children.
+struct Node<T> {
+    extra: T,
+    label: String,
+    parent: Option<usize>,
+    children: Option<Vec<usize>>,
+    expanded: bool,
+}
+
+impl<T> TreeItem<T> {
+    /// Creates an item without children.
+    pub fn leaf(extra: T, label: &str) -> TreeItem<T> {
+        return TreeItem {
+            extra,
+            label: String::from(label),
+            children: Some(vec![]),
+        };
+    }
+
+    /// Creates an item with the given children.
+    pub fn branch(extra: T, label: &str, children: Vec<TreeItem<T>>) -> TreeItem<T> {
+        return TreeItem {
+            extra,
+            label: String::from(label),
+            children: Some(children),
+        };
+    }
+
+    /// Creates an item whose children will be loaded when it is expanded.
+    pub fn lazy(extra: T, label: &str) -> TreeItem<T> {
+        return TreeItem {
+            extra,
+            label: String::from(label),
+            children: None,
+        };
+    }
+}
+
+impl<'a, T: Clone> TreeRect<'a, T> {
+    /// Clears the rect
+    pub fn clear(&self, stdout: &mut dyn Write) {
+        self.rect.clear(stdout);
+    }
+
+    /// Replaces the rect the tree is shown in, e.g. after the terminal was resized.
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.scroll_to_selected();
+    }
+
+    /// Set the items at the top of the tree. All items are collapsed.
+    pub fn set_items(&mut self, items: Vec<TreeItem<T>>) {
+        self.nodes = vec![];
+        self.roots = self.add(items, None);
+        self.visible = vec![];
+        self.cursor.selected = 0;
+        self.cursor.scroll = 0;
+        self.reflatten();
+    }
+
+    /// Adds the items and their children to the nodes and returns their indices.
+    fn add(&mut self, items: Vec<TreeItem<T>>, parent: Option<usize>) -> Vec<usize> {
+        let mut indices = vec![];
+        for item in items {
+            let index = self.nodes.len();
+            self.nodes.push(Node {
+                extra: item.extra,
+                label: item.label,
+                parent,
+                children: None,
+                expanded: false,
+            });
+            if let Some(children) = item.children {
+                self.nodes[index].children = Some(self.add(children, Some(index)));
+            }
+            indices.push(index);
+        }
+        return indices;
+    }
+
+    /// Sets the function that loads the children of an item created with [TreeItem.lazy].
+    /// It takes the extra information of the item and is called when the item is expanded for
+    /// the first time. Without a loader, such items have no children.
+    pub fn set_loader(&mut self, loader: Loader<'a, T>) {
+        self.loader = Some(loader);
+    }
+
+    /// Sets the theme.
+    /// The theme is a function that takes the extra information given with each item and whether it is selected
+    /// and returns the foreground color and background color
+    pub fn set_theme(&mut self, theme: Theme<'a, T>) {
+        self.theme = theme;
+    }
+
+    /// Sets whether lines connecting the items with their parents are shown. The default is `true`.
+    /// Without them, the items are only indented.
+    pub fn set_guides(&mut self, guides: bool) {
+        self.guides = guides;
+    }
+
+    /// Computes the shown items. The selected item stays selected, if it was hidden by collapsing
+    /// one of its parents, the parent is selected.
+    fn reflatten(&mut self) {
+        let mut selected = self.visible.get(self.cursor.selected).copied();
+
+        let mut visible = vec![];
+        let mut stack: Vec<usize> = self.roots.iter().rev().copied().collect();
+        while let Some(index) = stack.pop() {
+            visible.push(index);
+            if let (true, Some(children)) = (self.nodes[index].expanded, &self.nodes[index].children) {
+                stack.extend(children.iter().rev());
+            }
+        }
+        self.visible = visible;
+
+        let mut position = None;
+        while let Some(index) = selected {
+            position = self.visible.iter().position(|visible| *visible == index);
+            if position.is_some() {
+                break;
+            }
+            selected = self.nodes.get(index).and_then(|node| node.parent);
+        }
+        self.cursor.selected = position.unwrap_or(0);
+        self.scroll_to_selected();
+    }
+
+    /// Whether the item has or might have children.
+    fn is_expandable(&self, index: usize) -> bool {
+        return match &self.nodes[index].children {
+            Some(children) => !children.is_empty(),
+            None => self.loader.is_some(),
+        };
+    }
+
+    /// Expands the selected item. Its children are loaded if they are not known yet.
+    pub fn expand(&mut self) {
+        let Some(&index) = self.visible.get(self.cursor.selected) else {
+            return;
+        };
+        if self.nodes[index].children.is_none() {
+            let items = match self.loader {
+                Some(loader) => loader(self.nodes[index].extra.clone()),
+                None => vec![],
+            };
+            self.nodes[index].children = Some(self.add(items, Some(index)));
+        }
+        self.nodes[index].expanded = true;
+        self.reflatten();
+    }
+
+    /// Collapses the selected item.
+    pub fn collapse(&mut self) {
+        let Some(&index) = self.visible.get(self.cursor.selected) else {
+            return;
+        };
+        self.nodes[index].expanded = false;
+        self.reflatten();
+    }
+
+    /// Expands the selected item if it is collapsed, otherwise collapses it.
+    pub fn toggle(&mut self) {
+        match self.visible.get(self.cursor.selected) {
+            Some(&index) if self.nodes[index].expanded => self.collapse(),
+            Some(_) => self.expand(),
+            None => {}
+        }
+    }
+
+    /// Get the string of the currently selected item.
+    pub fn get_selected(&self) -> Option<String> {
+        let index = *self.visible.get(self.cursor.selected)?;
+        return Some(self.nodes[index].label.clone());
+    }
+
+    /// Get the extra information of the currently selected item.
+    pub fn get_selected_extra(&self) -> Option<T> {
+        let index = *self.visible.get(self.cursor.selected)?;
+        return Some(self.nodes[index].extra.clone());
+    }
+
+    /// Get the extra information of the currently selected item and all its parents, starting
+    /// with the item at the top of the tree.
+    pub fn get_selected_path(&self) -> Vec<T> {
+        let mut path = vec![];
+        let mut current = self.visible.get(self.cursor.selected).copied();
+        while let Some(index) = current {
+            path.push(self.nodes[index].extra.clone());
+            current = self.nodes[index].parent;
+        }
+        path.reverse();
+        return path;
+    }
+
+    /// The amount of items that can be shown.
+    fn height(&self) -> usize {
+        return self.rect.get_dimensions().1 as usize;
+    }
+
+    /// Scrolls as little as possible to show the selected item.
+    fn scroll_to_selected(&mut self) {
+        self.cursor.scroll_to_selected(self.visible.len(), self.height());
+    }
+
+    /// Moves the cursor to the next shown item. Stops at the last item.
+    pub fn next(&mut self) {
+        self.cursor.next(self.visible.len(), self.height());
+    }
+
+    /// Moves the cursor to the previous shown item. Stops at the first item.
+    pub fn prev(&mut self) {
+        self.cursor.prev(self.visible.len(), self.height());
+    }
+
+    /// Moves the cursor down by the amount of items shown. Stops at the last item.
+    pub fn page_down(&mut self) {
+        self.cursor.page_down(self.visible.len(), self.height());
+    }
+
+    /// Moves the cursor up by the amount of items shown. Stops at the first item.
+    pub fn page_up(&mut self) {
+        self.cursor.page_up(self.visible.len(), self.height());
+    }
+
+    /// Moves the cursor to the first item.
+    pub fn first(&mut self) {
+        self.cursor.first(self.visible.len(), self.height());
+    }
+
+    /// Moves the cursor to the last shown item.
+    pub fn last(&mut self) {
+        self.cursor.last(self.visible.len(), self.height());
+    }
+
+    /// Moves the cursor to the parent of the selected item.
+    pub fn parent(&mut self) {
+        let Some(&index) = self.visible.get(self.cursor.selected) else {
+            return;
+        };
+        if let Some(parent) = self.nodes[index].parent {
+            self.cursor.selected = self.visible.iter().position(|v| *v == parent).unwrap_or(0);
+            self.scroll_to_selected();
+        }
+    }
+
+    /// Moves the cursor or expands and collapses items according to the key.
+    /// Right expands the selected item or moves to its first child, left collapses it or moves
+    /// to its parent. Enter and space toggle the selected item. Returns whether the key was used.
+    pub fn handle_key(&mut self, key: Key) -> bool {
+        let index = self.visible.get(self.cursor.selected).copied();
+        match key {
+            Key::Down => self.next(),
+            Key::Up => self.prev(),
+            Key::PageDown => self.page_down(),
+            Key::PageUp => self.page_up(),
+            Key::Home => self.first(),
+            Key::End => self.last(),
+            Key::Right => match index {
+                Some(index) if self.nodes[index].expanded => self.next(),
+                Some(index) if self.is_expandable(index) => self.expand(),
+                _ => return false,
+            },
+            Key::Left => match index {
+                Some(index) if self.nodes[index].expanded => self.collapse(),
+                Some(_) => self.parent(),
+                None => return false,
+            },
+            Key::Char('\n') | Key::Char(' ') => self.toggle(),
+            _ => return false,
+        }
+        return true;
+    }
+
+    /// Whether the item is the last one among its siblings.
+    fn is_last(&self, index: usize) -> bool {
+        let siblings = match self.nodes[index].parent {
+            Some(parent) => self.nodes[parent].children.as_ref().unwrap_or(&self.roots),
+            None => &self.roots,
+        };
+        return siblings.last() == Some(&index);
+    }
+
+    /// Get the indentation and the marker shown in front of the label of the item.
+    fn prefix(&self, index: usize) -> String {
+        let mut indent = vec![];
+        let mut current = self.nodes[index].parent;
+        while let Some(parent) = current {
+            let guide = if self.guides && !self.is_last(parent) && self.nodes[parent].parent.is_some() {
+                "│ "
+            } else {
+                "  "
+            };
+            indent.push(guide);
+            current = self.nodes[parent].parent;
+        }
+        indent.pop();
+        indent.reverse();
+
+        let mut prefix = indent.concat();
+        if self.nodes[index].parent.is_some() {
+            prefix.push_str(match (self.guides, self.is_last(index)) {
+                (false, _) => "  ",
+                (true, false) => "├ ",
+                (true, true) => "└ ",
+            });
+        }
+
+        let node = &self.nodes[index];
+        prefix.push_str(if !self.is_expandable(index) {
+            "  "
+        } else if node.expanded {
+            "▾ "
+        } else {
+            "▸ "
+        });
+        return prefix;
+    }
+
+    /// Show the tree.
+    pub fn show(&self, stdout: &mut dyn Write) {
+        self.rect.show_border(stdout);
+
+        let (width, height) = self.rect.get_dimensions();
+        for y in 0..height as usize {
+            let position = self.cursor.scroll + y;
+            match self.visible.get(position) {
+                Some(&index) => {
+                    let node = &self.nodes[index];
+                    let (fg_color, bg_color) = (self.theme)(node.extra.clone(), position == self.cursor.selected);
+                    let line = format!("{}{}", self.prefix(index), node.label);
+                    self.rect
+                        .write_colored_trimmed_line(stdout, &line, y as u16, fg_color, bg_color)
+                        .unwrap();
+                }
+                None => {
+                    self.rect
+                        .write(stdout, &" ".repeat(width as usize), 0, y as u16)
+                        .unwrap();
+                }
+            }
+        }
+    }
+}
+
+impl crate::rect::Rect {
+    /// Creates a tree from the rect.
+    pub fn into_tree<'a, T>(self, theme: Theme<'a, T>) -> Box<TreeRect<'a, T>> {
+        return Box::new(TreeRect::<T> {
+            rect: self,
+            nodes: vec![],
+            roots: vec![],
+            visible: vec![],
+            cursor: Cursor::new(),
+            theme,
+            loader: None,
+            guides: true,
+        });
+    }
+}
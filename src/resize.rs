@@ -0,0 +1,135 @@
+extern crate signal_hook;
+
+use crate::layout;
+use crate::rect::Rect;
+
+use std::collections::HashMap;
+use std::io::Error;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A callback notified with the recomputed [Rect]s after the terminal was resized.
+/// If the layout does not fit in the new size, the [LayoutError] is given instead.
+pub type ResizeCallback<'a> =
+    Box<dyn FnMut(Result<&HashMap<String, Rect>, &layout::LayoutError>) + 'a>;
+
+/// Keeps the [Rect]s of a [Layout] in sync with the size of the terminal.
+/// The terminal is watched for `SIGWINCH`. When [Resizer.update] notices that the terminal was
+/// resized, all [Rect]s will be recomputed from the original [Layout] and the callbacks registered
+/// with [Resizer.on_resize] will be called.
+/// If the terminal gets too small for the layout, there will be no [Rect]s and the error can be
+/// shown with [Resizer.show_error] until the terminal is big enough again.
+pub struct Resizer<'a> {
+    layout: layout::Layout,
+    rects: HashMap<String, Rect>,
+    error: Option<layout::LayoutError>,
+    size: (u16, u16),
+    resized: Arc<AtomicBool>,
+    signal: signal_hook::SigId,
+    callbacks: Vec<ResizeCallback<'a>>,
+}
+
+impl<'a> Resizer<'a> {
+    /// Create a new [Resizer] for the given [Layout] and start watching for `SIGWINCH`.
+    /// The [Rect]s will be computed for the current size of the terminal.
+    pub fn new(layout: layout::Layout) -> Result<Resizer<'a>, Error> {
+        let resized = Arc::new(AtomicBool::new(false));
+        let signal =
+            signal_hook::flag::register(signal_hook::consts::SIGWINCH, Arc::clone(&resized))?;
+        let mut resizer = Resizer {
+            layout,
+            rects: HashMap::new(),
+            error: None,
+            size: Rect::terminal_size(),
+            resized,
+            signal,
+            callbacks: vec![],
+        };
+        resizer.relayout();
+
+        return Ok(resizer);
+    }
+
+    /// Register a callback that will be called with the new [Rect]s every time the terminal was resized.
+    pub fn on_resize(&mut self, callback: ResizeCallback<'a>) {
+        self.callbacks.push(callback);
+    }
+
+    /// Checks if the terminal was resized since the last call.
+    /// If it was, the [Rect]s will be recomputed, the callbacks will be called and `true` will be
+    /// returned. This should be called regularly, e.g. once in every iteration of the main loop.
+    pub fn update(&mut self) -> bool {
+        if !self.resized.swap(false, Ordering::SeqCst) {
+            return false;
+        }
+
+        let size = Rect::terminal_size();
+        if size == self.size {
+            return false;
+        }
+
+        self.size = size;
+        self.relayout();
+
+        for callback in self.callbacks.iter_mut() {
+            match &self.error {
+                None => callback(Ok(&self.rects)),
+                Some(error) => callback(Err(error)),
+            }
+        }
+
+        return true;
+    }
+
+    /// Recomputes the [Rect]s for the current size.
+    fn relayout(&mut self) {
+        match Rect::try_from_layout(&self.layout, 1, 1, self.size.0, self.size.1) {
+            Ok(rects) => {
+                self.rects = rects;
+                self.error = None;
+            }
+            Err(error) => {
+                self.rects = HashMap::new();
+                self.error = Some(error);
+            }
+        }
+    }
+
+    /// Get all current [Rect]s. The keys are the same as in [Rect.from_layout].
+    pub fn get_rects(&self) -> &HashMap<String, Rect> {
+        return &self.rects;
+    }
+
+    /// Get the current [Rect] with the given name.
+    pub fn get_rect(&self, name: &str) -> Option<&Rect> {
+        return self.rects.get(name);
+    }
+
+    /// Get the error if the layout does not fit in the terminal at the moment.
+    pub fn get_error(&self) -> Option<&layout::LayoutError> {
+        return self.error.as_ref();
+    }
+
+    /// Shows the error in the terminal if there is one. Returns whether an error was shown.
+    pub fn show_error(&self, stdout: &mut dyn Write) -> bool {
+        match &self.error {
+            Some(error) => {
+                Rect::show_layout_error(stdout, error);
+                return true;
+            }
+            None => return false,
+        }
+    }
+
+    /// Get the current size of the terminal. The tupel is ordered (width, height).
+    pub fn get_size(&self) -> (u16, u16) {
+        return self.size;
+    }
+}
+
+impl Drop for Resizer<'_> {
+    fn drop(&mut self) {
+        signal_hook::low_level::unregister(self.signal);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::rect::Rect;
+use crate::style::Style;
+
+use std::cmp;
+use std::io::Write;
+
+/// A scrollbar with a thin line as track.
+pub const LINED: Scrollbar = Scrollbar {
+    track: '│',
+    thumb: '█',
+    track_style: Style::new(),
+    thumb_style: Style::new(),
+    placement: Placement::BORDER,
+};
+
+/// A scrollbar with a shaded track.
+pub const SHADED: Scrollbar = Scrollbar {
+    track: '░',
+    thumb: '█',
+    track_style: Style::new(),
+    thumb_style: Style::new(),
+    placement: Placement::BORDER,
+};
+
+/// Where a [Scrollbar] is drawn.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Placement {
+    /// On the right border of the [Rect]. Without a right border, the last column is used instead.
+    BORDER,
+    /// In the last column of the [Rect]. The content is shown in the columns left of it.
+    INSIDE,
+}
+
+/// The style of a vertical scrollbar.
+/// It shows which part of the content is visible and is only drawn if the content does not fit.
+/// The track and the thumb are drawn with their [Style] on top of the [Style] of the [Rect].
+#[derive(Clone)]
+pub struct Scrollbar {
+    pub track: char,
+    pub thumb: char,
+    pub track_style: Style,
+    pub thumb_style: Style,
+    pub placement: Placement,
+}
+
+impl Scrollbar {
+    /// Whether the scrollbar takes up the last column of the given [Rect].
+    fn is_inside(&self, rect: &Rect) -> bool {
+        return self.placement == Placement::INSIDE || !rect.shows_border() || !rect.get_sides().east;
+    }
+
+    /// Get the [Rect] the content can be shown in next to the scrollbar.
+    pub fn content(&self, rect: &Rect) -> Rect {
+        if self.is_inside(rect) {
+            return rect.without_columns(1);
+        }
+        return rect.clone();
+    }
+
+    /// Get the first row and the amount of rows of the thumb, for the given amount of rows of
+    /// content of which the rows starting at `offset` are shown in `height` rows.
+    pub fn thumb(height: usize, total: usize, offset: usize) -> (usize, usize) {
+        if total <= height {
+            return (0, height);
+        }
+        let size = cmp::min(cmp::max(height * height / total, 1), height);
+        let max_offset = total - height;
+        let offset = cmp::min(offset, max_offset);
+        let start = ((height - size) * offset + max_offset / 2) / max_offset;
+        return (start, size);
+    }
+
+    /// Shows the scrollbar for the given [Rect]. `total` is the amount of rows of the content,
+    /// `offset` the first row that is shown. Nothing is drawn if all the content fits.
+    pub fn show(&self, stdout: &mut dyn Write, rect: &Rect, total: usize, offset: usize) {
+        let (width, height) = rect.get_dimensions();
+        if total <= height as usize || width == 0 {
+            return;
+        }
+
+        let (x, y) = rect.get_origin();
+        let x = if self.is_inside(rect) { x + width - 1 } else { rect.outer().2 };
+        let (start, size) = Scrollbar::thumb(height as usize, total, offset);
+
+        for row in 0..height {
+            let on_thumb = (start..start + size).contains(&(row as usize));
+            let (symbol, style) = if on_thumb {
+                (self.thumb, &self.thumb_style)
+            } else {
+                (self.track, &self.track_style)
+            };
+            let style = rect.get_style().patch(style);
+            write!(
+                stdout,
+                "{}{}{}{}",
+                termion::cursor::Goto(x, y + row),
+                style,
+                symbol,
+                style.reset()
+            )
+            .unwrap();
+        }
+    }
+}
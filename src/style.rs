@@ -0,0 +1,148 @@
+use std::fmt;
+
+/// The colors and modifiers text is written with. Everything that is not set stays as it is in
+/// the terminal.
+/// A [Style] is built from [Style.new] with what should be set,
+/// e.g. `Style::new().fg(&color::Red).modifier(Modifier::BOLD)`.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Style {
+    fg: Option<String>,
+    bg: Option<String>,
+    modifiers: u16,
+}
+
+/// Represents the ways text can be shown besides its colors.
+/// Not every terminal supports all of them.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Modifier {
+    BOLD,
+    DIM,
+    ITALIC,
+    UNDERLINE,
+    BLINK,
+    REVERSE,
+    HIDDEN,
+    CROSSED,
+}
+
+impl Modifier {
+    /// Get the parameters of the escape sequences that turn the modifier on and off.
+    fn codes(&self) -> (u8, u8) {
+        return match self {
+            Modifier::BOLD => (1, 22),
+            Modifier::DIM => (2, 22),
+            Modifier::ITALIC => (3, 23),
+            Modifier::UNDERLINE => (4, 24),
+            Modifier::BLINK => (5, 25),
+            Modifier::REVERSE => (7, 27),
+            Modifier::HIDDEN => (8, 28),
+            Modifier::CROSSED => (9, 29),
+        };
+    }
+}
+
+const MODIFIERS: [Modifier; 8] = [
+    Modifier::BOLD,
+    Modifier::DIM,
+    Modifier::ITALIC,
+    Modifier::UNDERLINE,
+    Modifier::BLINK,
+    Modifier::REVERSE,
+    Modifier::HIDDEN,
+    Modifier::CROSSED,
+];
+
+impl Style {
+    /// Create a new [Style] that does not change anything.
+    pub const fn new() -> Style {
+        return Style {
+            fg: None,
+            bg: None,
+            modifiers: 0,
+        };
+    }
+
+    /// Create a new [Style] with the given colors.
+    /// [termion::color::Reset] leaves a color unset, so it is taken from the surrounding [Style]
+    /// when combined with [Style.patch].
+    pub fn from_colors(fg_color: &dyn termion::color::Color, bg_color: &dyn termion::color::Color) -> Style {
+        let mut style = Style::new();
+        if format!("{}", termion::color::Fg(fg_color)) != format!("{}", termion::color::Fg(termion::color::Reset)) {
+            style = style.fg(fg_color);
+        }
+        if format!("{}", termion::color::Bg(bg_color)) != format!("{}", termion::color::Bg(termion::color::Reset)) {
+            style = style.bg(bg_color);
+        }
+        return style;
+    }
+
+    /// Get a copy of this [Style] changed by the other one. The colors set in the other [Style]
+    /// replace these, the modifiers of both are combined.
+    pub fn patch(&self, other: &Style) -> Style {
+        return Style {
+            fg: other.fg.clone().or_else(|| self.fg.clone()),
+            bg: other.bg.clone().or_else(|| self.bg.clone()),
+            modifiers: self.modifiers | other.modifiers,
+        };
+    }
+
+    /// Sets the foreground color.
+    pub fn fg(mut self, color: &dyn termion::color::Color) -> Style {
+        self.fg = Some(format!("{}", termion::color::Fg(color)));
+        return self;
+    }
+
+    /// Sets the background color.
+    pub fn bg(mut self, color: &dyn termion::color::Color) -> Style {
+        self.bg = Some(format!("{}", termion::color::Bg(color)));
+        return self;
+    }
+
+    /// Adds a [Modifier]. Several modifiers can be combined.
+    pub fn modifier(mut self, modifier: Modifier) -> Style {
+        self.modifiers |= 1 << modifier.codes().0;
+        return self;
+    }
+
+    /// Whether the [Style] has the [Modifier].
+    pub fn has_modifier(&self, modifier: Modifier) -> bool {
+        return self.modifiers & (1 << modifier.codes().0) != 0;
+    }
+
+    /// Get the escape sequences that reset everything this [Style] changed.
+    pub fn reset(&self) -> String {
+        let mut reset = String::new();
+        if self.fg.is_some() {
+            reset.push_str(&format!("{}", termion::color::Fg(termion::color::Reset)));
+        }
+        if self.bg.is_some() {
+            reset.push_str(&format!("{}", termion::color::Bg(termion::color::Reset)));
+        }
+        let mut codes: Vec<u8> = MODIFIERS
+            .iter()
+            .filter(|m| self.has_modifier(**m))
+            .map(|m| m.codes().1)
+            .collect();
+        codes.dedup();
+        for code in codes {
+            reset.push_str(&format!("\x1B[{}m", code));
+        }
+        return reset;
+    }
+}
+
+/// Writes the escape sequences that set this [Style].
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for modifier in MODIFIERS.iter().filter(|m| self.has_modifier(**m)) {
+            write!(f, "\x1B[{}m", modifier.codes().0)?;
+        }
+        if let Some(fg) = &self.fg {
+            write!(f, "{}", fg)?;
+        }
+        if let Some(bg) = &self.bg {
+            write!(f, "{}", bg)?;
+        }
+        return Ok(());
+    }
+}
@@ -0,0 +1,239 @@
+extern crate unicode_segmentation;
+extern crate unicode_width;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Get the amount of columns the string takes up in the terminal.
+pub fn width(str: &str) -> usize {
+    return UnicodeWidthStr::width(str);
+}
+
+/// Get the amount of columns the first grapheme cluster of the string takes up.
+pub fn first_width(str: &str) -> usize {
+    return str.graphemes(true).next().map(width).unwrap_or(0);
+}
+
+/// Get the amount of grapheme clusters, i.e. user perceived characters, in the string.
+pub fn grapheme_count(str: &str) -> usize {
+    return str.graphemes(true).count();
+}
+
+/// Get the byte index at which the grapheme cluster with the given index starts.
+/// If there are less grapheme clusters, the length of the string will be returned.
+pub fn byte_index(str: &str, index: usize) -> usize {
+    return str
+        .grapheme_indices(true)
+        .nth(index)
+        .map(|(i, _)| i)
+        .unwrap_or(str.len());
+}
+
+/// Splits the string such that the first part takes up at most the given amount of columns.
+/// The string will only be split between grapheme clusters.
+pub fn split_at_width(str: &str, columns: usize) -> (&str, &str) {
+    let mut used = 0;
+    for (i, grapheme) in str.grapheme_indices(true) {
+        used += width(grapheme);
+        if used > columns {
+            return str.split_at(i);
+        }
+    }
+    return (str, "");
+}
+
+/// Shortens the string to the given amount of columns. If the string does not fit, the end
+/// will be replaced with "...".
+pub fn trim(str: &str, columns: usize) -> String {
+    if width(str) <= columns {
+        return String::from(str);
+    }
+    if columns < 3 {
+        return ".".repeat(columns);
+    }
+    return format!("{}...", split_at_width(str, columns - 3).0);
+}
+
+/// Represents where text is placed in a space wider than the text.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Alignment {
+    LEFT,
+    CENTER,
+    RIGHT,
+}
+
+/// Shortens the string to the given amount of columns like [trim] and fills the remaining
+/// columns with spaces, such that the text is placed according to the alignment.
+pub fn align(str: &str, columns: usize, alignment: Alignment) -> String {
+    let str = trim(str, columns);
+    let space = columns - width(&str);
+    let left = match alignment {
+        Alignment::LEFT => 0,
+        Alignment::CENTER => space / 2,
+        Alignment::RIGHT => space,
+    };
+    return format!("{}{}{}", " ".repeat(left), str, " ".repeat(space - left));
+}
+
+/// Represents how text is wrapped when it does not fit in a line.
+/// `CHARACTER` breaks at the last character that fits, `WORD` breaks between words and
+/// `HYPHENATE` additionally puts a "-" where a word longer than a line is broken.
+/// `NONE` does not wrap at all, everything not fitting in the line is cut off.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum WrapMode {
+    CHARACTER,
+    WORD,
+    HYPHENATE,
+    NONE,
+}
+
+/// Wraps the string into lines. The first line has `first` columns, all others `columns`.
+/// Newlines in the string always start a new line.
+pub fn wrap(str: &str, first: usize, columns: usize, mode: WrapMode) -> Vec<String> {
+    let mut lines = vec![];
+    for paragraph in str.split('\n') {
+        let available = if lines.is_empty() { first } else { columns };
+        match mode {
+            WrapMode::NONE => lines.push(String::from(split_at_width(paragraph, available).0)),
+            WrapMode::CHARACTER => wrap_characters(paragraph, available, columns, &mut lines),
+            WrapMode::WORD | WrapMode::HYPHENATE => {
+                wrap_words(paragraph, available, columns, mode, &mut lines)
+            }
+        }
+    }
+    return lines;
+}
+
+/// Wraps at the last character that fits in a line.
+fn wrap_characters(str: &str, first: usize, columns: usize, lines: &mut Vec<String>) {
+    let mut rest = str;
+    let mut available = first;
+    loop {
+        let (mut line, mut next) = split_at_width(rest, available);
+        if line.is_empty() && !next.is_empty() && available >= columns {
+            // A single character wider than a line, skip it.
+            line = "";
+            next = split_at_width(next, first_width(next)).1;
+        }
+        lines.push(String::from(line));
+        if next.is_empty() {
+            return;
+        }
+        rest = next;
+        available = columns;
+    }
+}
+
+/// Wraps between words. Words longer than a line are broken, with a "-" if hyphenating.
+fn wrap_words(str: &str, first: usize, columns: usize, mode: WrapMode, lines: &mut Vec<String>) {
+    let mut line = String::new();
+    let mut available = first;
+
+    for word in str.split(' ') {
+        let separator = if line.is_empty() { "" } else { " " };
+        if width(&line) + width(separator) + width(word) <= available {
+            line.push_str(separator);
+            line.push_str(word);
+            continue;
+        }
+
+        if width(word) <= columns {
+            // The word fits in the next line.
+            lines.push(std::mem::take(&mut line));
+            line.push_str(word);
+            available = columns;
+            continue;
+        }
+
+        // The word is longer than a line and has to be broken, starting in the current line.
+        line.push_str(separator);
+        let mut rest = word;
+        loop {
+            let space = available.saturating_sub(width(&line));
+            let mut hyphen = mode == WrapMode::HYPHENATE && space > 1 && width(rest) > space;
+            let (mut part, mut next) = split_at_width(rest, space - hyphen as usize);
+            if hyphen && part.is_empty() {
+                // Not even one character fits in front of the hyphen.
+                hyphen = false;
+                (part, next) = split_at_width(rest, space);
+            }
+            line.push_str(part);
+            if hyphen {
+                line.push('-');
+            }
+            if next.is_empty() {
+                break;
+            }
+            if part.is_empty() && line.is_empty() && available >= columns {
+                // A single character wider than a line, skip it.
+                rest = split_at_width(next, first_width(next)).1;
+                continue;
+            }
+            lines.push(std::mem::take(&mut line));
+            available = columns;
+            rest = next;
+        }
+    }
+
+    lines.push(line);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        return lines.iter().map(|line| String::from(*line)).collect();
+    }
+
+    #[test]
+    fn wrap_words_between_words() {
+        assert_eq!(wrap("ab cd ef", 5, 5, WrapMode::WORD), lines(&["ab cd", "ef"]));
+    }
+
+    #[test]
+    fn wrap_words_long_word_without_room_in_first_line() {
+        assert_eq!(
+            wrap("abcdefghijklmnop", 0, 5, WrapMode::WORD),
+            lines(&["", "abcde", "fghij", "klmno", "p"])
+        );
+        assert_eq!(
+            wrap("日本語テキスト", 1, 6, WrapMode::WORD),
+            lines(&["", "日本語", "テキス", "ト"])
+        );
+    }
+
+    #[test]
+    fn wrap_words_skips_character_wider_than_a_line() {
+        assert_eq!(wrap("a日b", 1, 1, WrapMode::WORD), lines(&["a", "b"]));
+    }
+
+    #[test]
+    fn hyphenate_long_word() {
+        assert_eq!(
+            wrap("abcdefgh", 4, 4, WrapMode::HYPHENATE),
+            lines(&["abc-", "def-", "gh"])
+        );
+    }
+
+    #[test]
+    fn hyphenate_starts_in_current_line() {
+        assert_eq!(
+            wrap("ab cdefghij", 6, 6, WrapMode::HYPHENATE),
+            lines(&["ab cd-", "efghij"])
+        );
+    }
+
+    #[test]
+    fn hyphenate_without_room_for_hyphen() {
+        assert_eq!(
+            wrap("ab cdefgh", 4, 4, WrapMode::HYPHENATE),
+            lines(&["ab c", "def-", "gh"])
+        );
+    }
+
+    #[test]
+    fn hyphenate_wide_character_in_front_of_hyphen() {
+        assert_eq!(wrap("日本語", 2, 2, WrapMode::HYPHENATE), lines(&["日", "本", "語"]));
+    }
+}